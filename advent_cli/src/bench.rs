@@ -0,0 +1,207 @@
+//! Benchmarks solutions, timing the input reading, parsing and solving separately
+
+use clap::Args;
+use std::time::{Duration, Instant};
+
+use advent_common::{
+    command::Answer,
+    errors::{AdventErrors, AdventResult},
+    input::InputSource,
+    registry::{DaySelection, Part, SolutionEntry},
+};
+
+use crate::{cli::select_solutions, config::Settings, output::format_answer};
+
+/// Arguments of the `bench` command
+#[derive(Args, Clone, Debug)]
+pub struct BenchArgs {
+    /// Day to benchmark, or an inclusive range such as `3..5`
+    days: DaySelection,
+    /// Part to benchmark (`a` or `b`). Both parts run when omitted
+    part: Option<Part>,
+    /// Path to the input file, or `-` to read stdin. Searched for when not given
+    #[arg(short, long)]
+    input: Option<InputSource>,
+    /// Number of timed runs. Defaults to the `bench.iterations` setting
+    #[arg(short = 'n', long, value_parser = clap::value_parser!(u32).range(1..))]
+    iterations: Option<u32>,
+    /// Number of untimed runs before measuring. Defaults to the `bench.warmup` setting
+    #[arg(short, long)]
+    warmup: Option<u32>,
+}
+
+/// Summary statistics over a set of timings
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// # Return
+    /// `None` when there are no samples
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2
+        } else {
+            sorted[mid]
+        };
+
+        let secs: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / secs.len() as f64;
+        let variance = secs
+            .iter()
+            .map(|sample| (sample - mean).powi(2))
+            .sum::<f64>()
+            / secs.len() as f64;
+
+        Some(Self {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// Timings of every measured iteration of one solution
+#[derive(Debug, Default)]
+struct PhaseSamples {
+    read: Vec<Duration>,
+    parse: Vec<Duration>,
+    solve: Vec<Duration>,
+    total: Vec<Duration>,
+}
+
+impl PhaseSamples {
+    /// Times one iteration, returning the solution's answer
+    fn measure(&mut self, entry: &SolutionEntry, source: &InputSource) -> AdventResult<Answer> {
+        let start = Instant::now();
+        let input = source.read_to_string()?;
+        let read = start.elapsed();
+
+        let start = Instant::now();
+        let parsed = (entry.parse)(&input).map_err(|err| err.in_file(source.label()))?;
+        let parse = start.elapsed();
+
+        let start = Instant::now();
+        let answer = (entry.solve)(&parsed)?;
+        let solve = start.elapsed();
+
+        self.read.push(read);
+        self.parse.push(parse);
+        self.solve.push(solve);
+        self.total.push(read + parse + solve);
+        Ok(answer)
+    }
+
+    fn report(&self) -> String {
+        let phases = [
+            ("read", &self.read),
+            ("parse", &self.parse),
+            ("solve", &self.solve),
+            ("total", &self.total),
+        ];
+
+        let mut lines = vec![format!(
+            "  {:<6}{:>12}{:>12}{:>12}{:>12}",
+            "Phase", "Min", "Median", "Mean", "Stddev"
+        )];
+        for (name, samples) in phases {
+            let Some(stats) = Stats::from_samples(samples) else {
+                continue;
+            };
+            lines.push(format!(
+                "  {:<6}{:>12}{:>12}{:>12}{:>12}",
+                name,
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.mean),
+                format!("{:.2?}", stats.stddev),
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+fn benchmark_solution(
+    entry: &SolutionEntry,
+    args: &BenchArgs,
+    settings: &Settings,
+) -> AdventResult<()> {
+    let source = entry
+        .locator(args.input.clone(), settings.input_dir.clone())
+        .locate()?;
+    let iterations = args.iterations.unwrap_or(settings.bench.iterations);
+    let warmup = args.warmup.unwrap_or(settings.bench.warmup);
+
+    let mut warmup_samples = PhaseSamples::default();
+    for _ in 0..warmup {
+        warmup_samples.measure(entry, &source)?;
+    }
+
+    let mut samples = PhaseSamples::default();
+    let mut answer = None;
+    for _ in 0..iterations {
+        answer = Some(samples.measure(entry, &source)?);
+    }
+
+    if let Some(answer) = answer {
+        println!("{}: {}", entry.name(), format_answer(&answer));
+    }
+    println!("  {} iterations after {} warmup runs", iterations, warmup);
+    println!("{}", samples.report());
+    Ok(())
+}
+
+/// Runs the `bench` command
+pub(crate) fn run_benchmark(args: &BenchArgs, settings: &Settings) -> AdventResult<()> {
+    if args.iterations.unwrap_or(settings.bench.iterations) == 0 {
+        return Err(AdventErrors::ConfigError(
+            "bench.iterations must be at least 1".to_string(),
+        ));
+    }
+
+    let selected = select_solutions(
+        settings.year,
+        Some(args.days),
+        args.part,
+        args.input.as_ref(),
+    )?;
+    for entry in selected {
+        benchmark_solution(entry, args, settings)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_stats_from_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+
+        let samples: Vec<Duration> = [4, 1, 3, 2].map(Duration::from_millis).to_vec();
+        let stats = Stats::from_samples(&samples).expect("Samples are not empty");
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean.as_micros(), 2500);
+        // Population standard deviation of 1, 2, 3, 4 ms is sqrt(1.25) ms
+        assert_eq!(stats.stddev.as_micros(), 1118);
+
+        let single = Stats::from_samples(&[Duration::from_millis(7)]).expect("One sample");
+        assert_eq!(single.median, Duration::from_millis(7));
+        assert_eq!(single.stddev, Duration::ZERO);
+    }
+}
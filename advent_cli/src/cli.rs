@@ -1,58 +1,316 @@
 //! Interface for users to interact with this application
 //! Each command will query the server (via the client), and return the result
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    process::ExitCode,
+};
 
-use advent_common::{command::AdventSolution, errors::AdventResult};
+use advent_common::{
+    config::OutputFormat,
+    diagnostics::{render_diagnostic, render_error_chain},
+    errors::{AdventErrors, AdventResult},
+    input::InputSource,
+    logging,
+    registry::{self, DaySelection, Part, SolutionEntry},
+    tracing::debug,
+};
+
+use crate::{
+    bench::{run_benchmark, BenchArgs},
+    config::{run_config, ConfigArgs, GlobalArgs, Settings},
+    fetch::{run_fetch, FetchArgs},
+    output::{format_answer, format_csv, format_json},
+    scaffold::{run_new, NewArgs},
+    submit::{run_submit, SubmitArgs},
+    summary::{diagnostics, RunOutcome, SummaryTable},
+    verify::{run_verify, VerifyArgs},
+};
+
+// Link every day so its solutions land in the registry
+use advent_day1 as _;
+use advent_day2 as _;
+use advent_day3 as _;
+use advent_day4 as _;
 
-/// CLI option for every day/binary.
-/// All should implement AdventSolution
 #[derive(Subcommand, Clone, Debug)]
 pub enum AdventCommands {
-    Day1a(advent_day1::solution::Day1a),
-    Day1b(advent_day1::solution::Day1b),
-    Day2a(advent_day2::solution::Day2a),
-    Day2b(advent_day2::solution::Day2b),
-    Day3a(advent_day3::solution::Day3a),
-    Day3b(advent_day3::solution::Day3b),
-    Day4a(advent_day4::solution::Day4a),
-    Day4b(advent_day4::solution::Day4b),
+    /// Run the solutions for one or more days
+    Run(RunArgs),
+    /// Time the reading, parsing and solving of a day's solutions
+    Bench(BenchArgs),
+    /// Rerun solutions and compare them against the recorded answers
+    Verify(VerifyArgs),
+    /// Inspect the configuration files
+    Config(ConfigArgs),
+    /// Create and register the crate of a new day
+    New(NewArgs),
+    /// Download a day's puzzle input, unless it is already saved
+    Fetch(FetchArgs),
+    /// Submit the answer of a solution and record it if it is accepted
+    Submit(SubmitArgs),
 }
 
-impl AdventSolution for AdventCommands {
-    fn find_solution(&self) -> AdventResult<String> {
-        use AdventCommands::*;
-
+impl AdventCommands {
+    /// Name of the command as typed on the command line
+    pub fn name(&self) -> &'static str {
         match self {
-            Day1a(cmd) => cmd.find_solution(),
-            Day1b(cmd) => cmd.find_solution(),
-            Day2a(cmd) => cmd.find_solution(),
-            Day2b(cmd) => cmd.find_solution(),
-            Day3a(cmd) => cmd.find_solution(),
-            Day3b(cmd) => cmd.find_solution(),
-            Day4a(cmd) => cmd.find_solution(),
-            Day4b(cmd) => cmd.find_solution(),
+            AdventCommands::Run(_) => "run",
+            AdventCommands::Bench(_) => "bench",
+            AdventCommands::Verify(_) => "verify",
+            AdventCommands::Config(_) => "config",
+            AdventCommands::New(_) => "new",
+            AdventCommands::Fetch(_) => "fetch",
+            AdventCommands::Submit(_) => "submit",
         }
     }
 }
 
+/// Arguments shared by every solution
+#[derive(Args, Clone, Debug)]
+pub struct RunArgs {
+    /// Day to run, or an inclusive range such as `3..5`
+    #[arg(required_unless_present = "all")]
+    days: Option<DaySelection>,
+    /// Part to run (`a` or `b`). Both parts run when omitted
+    part: Option<Part>,
+    /// Path to the input file, or `-` to read stdin. Searched for when not given
+    #[arg(short, long)]
+    input: Option<InputSource>,
+    /// Run every registered solution
+    #[arg(long, conflicts_with = "days")]
+    all: bool,
+}
+
+/// Solutions matching the requested year, days and part.
+/// Every day is selected when `days` is `None`.
+pub(crate) fn select_solutions(
+    year: Option<u16>,
+    days: Option<DaySelection>,
+    part: Option<Part>,
+    input: Option<&InputSource>,
+) -> AdventResult<Vec<&'static SolutionEntry>> {
+    let days = match days {
+        Some(days) => days,
+        None => DaySelection {
+            first: u8::MIN,
+            last: u8::MAX,
+        },
+    };
+    let selected = registry::select(year, days, part)?;
+    let single_day = selected.iter().all(|entry| entry.day == selected[0].day);
+    if input.is_some() && !single_day {
+        return Err(AdventErrors::UsageError(
+            "--input can only be used when running a single day".to_string(),
+        ));
+    }
+    Ok(selected)
+}
+
+impl RunArgs {
+    /// Solutions of `year` matching the requested days and part
+    fn selected_solutions(&self, year: Option<u16>) -> AdventResult<Vec<&'static SolutionEntry>> {
+        select_solutions(year, self.days, self.part, self.input.as_ref())
+    }
+}
+
+/// Solutions to Advent of Code
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub(crate) struct AdventCLI {
     #[clap(subcommand)]
     command: AdventCommands,
+    #[command(flatten)]
+    global: GlobalArgs,
+}
+
+/// Prints `output` on its own line.
+/// A closed stdout, i.e. when piped into `head`, is not an error.
+fn print_output(output: impl Display) -> AdventResult<()> {
+    match writeln!(io::stdout().lock(), "{}", output) {
+        Err(err) if err.kind() != io::ErrorKind::BrokenPipe => Err(err.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Runs the selected solutions.
+/// As text, a single solution prints its answer and several print a summary table.
+/// JSON and CSV print one record per solution, whether one or several ran.
+/// Every selected solution runs even when some fail.
+fn run_command(args: &RunArgs, settings: &Settings) -> AdventResult<()> {
+    let selected = args.selected_solutions(settings.year)?;
+
+    let mut outcomes =
+        RunOutcome::run_all(&selected, args.input.clone(), settings.input_dir.as_deref());
+    match settings.format {
+        OutputFormat::Text => {
+            if let [_] = outcomes[..] {
+                let answer = outcomes.remove(0).result?;
+                return print_output(format_answer(&answer));
+            }
+            print_output(SummaryTable::new(&outcomes))?;
+        }
+        OutputFormat::Json => print_output(format_json(&outcomes)?)?,
+        OutputFormat::Csv => print_output(format_csv(&outcomes))?,
+    }
+    for diagnostic in diagnostics(&outcomes) {
+        eprintln!("\n{}", diagnostic);
+    }
+
+    let total = outcomes.len();
+    let mut failures = outcomes
+        .into_iter()
+        .filter_map(|outcome| outcome.result.err());
+    match failures.next() {
+        None => Ok(()),
+        Some(first) => Err(AdventErrors::SolutionsFailed {
+            failed: 1 + failures.count(),
+            total,
+            first: Box::new(first),
+        }),
+    }
+}
+
+/// Runs the requested command with the settings of the configuration files and flags
+fn run_args(args: &AdventCLI) -> AdventResult<()> {
+    let settings = Settings::load(&args.global)?;
+    logging::init(settings.verbosity);
+    debug!(?settings, "Loaded settings");
+    match &args.command {
+        AdventCommands::Run(run_args) => run_command(run_args, &settings),
+        AdventCommands::Bench(bench_args) => run_benchmark(bench_args, &settings),
+        AdventCommands::Verify(verify_args) => run_verify(verify_args, &settings),
+        AdventCommands::Config(config_args) => run_config(config_args, &args.global),
+        AdventCommands::New(new_args) => run_new(new_args, &settings),
+        AdventCommands::Fetch(fetch_args) => run_fetch(fetch_args, &settings),
+        AdventCommands::Submit(submit_args) => run_submit(submit_args, &settings),
+    }
+}
+
+/// Report of `command` failing with `err`: the command and the error's causes,
+/// followed by the offending input when the error points into it
+fn render_failure(command: &str, err: &AdventErrors) -> String {
+    match render_diagnostic(err) {
+        Some(diagnostic) => format!("error: `{}` failed\n{}", command, diagnostic),
+        None => format!("error: `{}` failed: {}", command, render_error_chain(err)),
+    }
 }
 
 /// Entrance to the client by parsing CLI values and running commands
-pub fn run_cli() {
+///
+/// # Return
+/// The exit code of the process, see `advent_common::errors::exit_codes`
+pub fn run_cli() -> ExitCode {
     let args = AdventCLI::parse();
-    let cmd_res = args.command.find_solution();
-
-    match cmd_res {
+    match run_args(&args) {
+        Ok(()) => ExitCode::SUCCESS,
         Err(err) => {
-            println!("Error Running command : <print cmd>.\n Error: {:?}", err);
+            eprintln!("{}", render_failure(args.command.name(), &err));
+            ExitCode::from(err.exit_code())
         }
-        Ok(res) => {
-            println!("{}", res);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use advent_common::errors::exit_codes;
+    use clap::CommandFactory;
+
+    fn parse_run(args: &[&str]) -> RunArgs {
+        let cli = AdventCLI::try_parse_from(args).expect("Arguments should parse");
+        match cli.command {
+            AdventCommands::Run(run_args) => run_args,
+            other => panic!("Expected run command, got {:?}", other),
         }
     }
+
+    #[test]
+    fn test_cli_is_valid() {
+        AdventCLI::command().debug_assert();
+    }
+
+    #[test]
+    fn test_every_day_registered() {
+        let names: Vec<String> = registry::solutions()
+            .iter()
+            .map(|entry| entry.name())
+            .collect();
+        for day in 1..=4 {
+            assert!(names.contains(&format!("day{}a", day)), "{:?}", names);
+            assert!(names.contains(&format!("day{}b", day)), "{:?}", names);
+        }
+    }
+
+    #[test]
+    fn test_run_selection() {
+        let both_parts = parse_run(&["cli", "run", "3"])
+            .selected_solutions(None)
+            .expect("Day 3 is registered");
+        assert_eq!(both_parts.len(), 2);
+
+        let one_part = parse_run(&["cli", "run", "3", "b"])
+            .selected_solutions(None)
+            .expect("Day 3b is registered");
+        assert_eq!(one_part.len(), 1);
+        assert_eq!(one_part[0].name(), "day3b");
+
+        let range = parse_run(&["cli", "run", "2..4", "a"])
+            .selected_solutions(None)
+            .expect("Days 2-4 are registered");
+        let names: Vec<String> = range.iter().map(|entry| entry.name()).collect();
+        assert_eq!(names, vec!["day2a", "day3a", "day4a"]);
+    }
+
+    #[test]
+    fn test_run_all() {
+        let all = parse_run(&["cli", "run", "--all"])
+            .selected_solutions(None)
+            .expect("Solutions are registered");
+        assert_eq!(all.len(), registry::solutions().len());
+
+        assert!(AdventCLI::try_parse_from(["cli", "run"]).is_err());
+        assert!(AdventCLI::try_parse_from(["cli", "run", "3", "--all"]).is_err());
+    }
+
+    #[test]
+    fn test_input_requires_single_day() {
+        let args = parse_run(&["cli", "run", "1..2", "--input", "/tmp/input.txt"]);
+        let err = args.selected_solutions(None).expect_err("Several days");
+        assert_eq!(err.exit_code(), exit_codes::USAGE);
+
+        let args = parse_run(&["cli", "run", "2", "--input", "/tmp/input.txt"]);
+        assert!(args.selected_solutions(None).is_ok());
+    }
+
+    #[test]
+    fn test_failure_names_command() {
+        let plain = AdventErrors::SolutionNotImplemented("Not yet".to_string());
+        assert_eq!(
+            render_failure("run", &plain),
+            "error: `run` failed: Not yet"
+        );
+
+        let located = match registry::select(None, "3".parse().expect("A day"), Some(Part::A))
+            .expect("Day 3a is registered")[0]
+            .run("ab\nabc")
+        {
+            Err(err) => err,
+            Ok(answer) => panic!("Odd rucksack should not parse, got {:?}", answer),
+        };
+        let rendered = render_failure("run", &located);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[0], "error: `run` failed", "{}", rendered);
+        assert!(lines[1].starts_with("error: "), "{}", rendered);
+        assert!(rendered.contains("2 | abc"), "{}", rendered);
+    }
+
+    #[test]
+    fn test_new_day_in_range() {
+        assert!(AdventCLI::try_parse_from(["cli", "new", "5"]).is_ok());
+        assert!(AdventCLI::try_parse_from(["cli", "new", "0"]).is_err());
+        assert!(AdventCLI::try_parse_from(["cli", "new", "26"]).is_err());
+    }
 }
@@ -0,0 +1,499 @@
+//! Client of the Advent of Code website: downloads inputs and submits answers.
+//!
+//! Requests go through an `HttpBackend`, so tests can answer them with a local server.
+//! Requests are authenticated with the session cookie of a logged in browser, read from
+//! `ADVENT_SESSION` or else from `~/.config/advent/session`.
+
+use std::{env, fmt, fs, time::Duration};
+
+use advent_common::{
+    config::user_config_home,
+    errors::{AdventErrors, AdventResult},
+    registry::Part,
+    tracing::debug,
+};
+
+/// Address of the website
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+/// Environment variable overriding the address of the website, i.e. for a local stand-in
+pub const BASE_URL_ENV_VAR: &str = "ADVENT_BASE_URL";
+/// Environment variable holding the session cookie
+pub const SESSION_ENV_VAR: &str = "ADVENT_SESSION";
+/// File holding the session cookie, relative to the user's config directory
+pub const SESSION_FILE_PATH: &str = "advent/session";
+
+/// Sent with every request, as asked by the website's maintainer
+const USER_AGENT: &str = "github.com/MatthewRizzo/advent-of-code-rust";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Status and body of an HTTP response
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Sends HTTP requests. Any status is returned as a response, only transport failures are errors.
+pub trait HttpBackend {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> AdventResult<HttpResponse>;
+
+    /// Posts `form` URL-encoded
+    fn post_form(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+        form: &[(&str, &str)],
+    ) -> AdventResult<HttpResponse>;
+}
+
+/// Backend sending the requests over the network
+#[derive(Clone, Debug)]
+pub struct UreqBackend {
+    agent: ureq::Agent,
+}
+
+impl Default for UreqBackend {
+    fn default() -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(TIMEOUT)
+                .build(),
+        }
+    }
+}
+
+impl UreqBackend {
+    fn response(result: Result<ureq::Response, ureq::Error>) -> AdventResult<HttpResponse> {
+        let response = match result {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(ureq::Error::Transport(err)) => {
+                return Err(AdventErrors::RequestError(err.to_string()))
+            }
+        };
+        Ok(HttpResponse {
+            status: response.status(),
+            body: response.into_string()?,
+        })
+    }
+}
+
+impl HttpBackend for UreqBackend {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> AdventResult<HttpResponse> {
+        let request = headers
+            .iter()
+            .fold(self.agent.get(url), |request, (name, value)| {
+                request.set(name, value)
+            });
+        Self::response(request.call())
+    }
+
+    fn post_form(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+        form: &[(&str, &str)],
+    ) -> AdventResult<HttpResponse> {
+        let request = headers
+            .iter()
+            .fold(self.agent.post(url), |request, (name, value)| {
+                request.set(name, value)
+            });
+        Self::response(request.send_form(form))
+    }
+}
+
+/// How the website judged a submitted answer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without saying in which direction
+    Incorrect,
+    /// An answer was submitted too recently, nothing was checked
+    RateLimited {
+        wait: Duration,
+    },
+    /// The part is already solved or not unlocked yet, nothing was checked
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Reads the verdict out of the page answering a submission
+    ///
+    /// # Return
+    /// `None` if the page does not look like an answer to a submission
+    pub fn from_page(page: &str) -> Option<Verdict> {
+        let text = page_text(page);
+        if text.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if text.contains("That's not the right answer") {
+            Some(if text.contains("too high") {
+                Verdict::TooHigh
+            } else if text.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Incorrect
+            })
+        } else if text.contains("You gave an answer too recently") {
+            let wait = text
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .and_then(|(wait, _)| parse_wait(wait))
+                .unwrap_or_default();
+            Some(Verdict::RateLimited { wait })
+        } else if text.contains("You don't seem to be solving the right level") {
+            Some(Verdict::WrongLevel)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::RateLimited { wait } => {
+                write!(
+                    f,
+                    "rate limited, wait {}s before submitting",
+                    wait.as_secs()
+                )
+            }
+            Verdict::WrongLevel => write!(f, "already solved or not unlocked yet"),
+        }
+    }
+}
+
+/// Text of the `<main>` element of `page`, or of the whole page, without tags or line breaks
+fn page_text(page: &str) -> String {
+    let main = page
+        .split_once("<main>")
+        .and_then(|(_, rest)| rest.split_once("</main>"))
+        .map_or(page, |(main, _)| main);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in main.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// Parses waits such as `38s`, `4m 2s` or `1h 10m`
+fn parse_wait(wait: &str) -> Option<Duration> {
+    wait.split_whitespace()
+        .map(|amount| {
+            let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+            let seconds_per_unit = match unit {
+                "s" => 1,
+                "m" => 60,
+                "h" => 60 * 60,
+                _ => return None,
+            };
+            Some(number.parse::<u64>().ok()? * seconds_per_unit)
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// The session cookie, from `ADVENT_SESSION` or else the session file
+pub fn session_token() -> AdventResult<String> {
+    if let Some(token) = env::var(SESSION_ENV_VAR)
+        .ok()
+        .filter(|token| !token.is_empty())
+    {
+        return Ok(token.trim().to_string());
+    }
+    let path = user_config_home().map(|dir| dir.join(SESSION_FILE_PATH));
+    match path.as_ref().map(fs::read_to_string) {
+        Some(Ok(token)) if !token.trim().is_empty() => Ok(token.trim().to_string()),
+        _ => Err(AdventErrors::RequestError(format!(
+            "no session token. Copy the `session` cookie of adventofcode.com into {} or {}",
+            SESSION_ENV_VAR,
+            path.map_or_else(
+                || format!("~/.config/{}", SESSION_FILE_PATH),
+                |path| path.display().to_string()
+            )
+        ))),
+    }
+}
+
+/// Requests puzzle data and submits answers as the logged in user
+#[derive(Clone, Debug)]
+pub struct AdventClient<B: HttpBackend> {
+    backend: B,
+    base_url: String,
+    session: String,
+}
+
+impl AdventClient<UreqBackend> {
+    /// Client of the website, or of `ADVENT_BASE_URL` when set, using the user's session token
+    pub fn from_env() -> AdventResult<Self> {
+        let base_url = env::var(BASE_URL_ENV_VAR)
+            .ok()
+            .filter(|url| !url.is_empty())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        Ok(Self::new(
+            UreqBackend::default(),
+            base_url,
+            session_token()?,
+        ))
+    }
+}
+
+impl<B: HttpBackend> AdventClient<B> {
+    pub fn new(backend: B, base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        Self {
+            backend,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+        }
+    }
+
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Page of `day`, i.e. `https://adventofcode.com/2022/day/3`
+    fn day_url(&self, year: u16, day: u8) -> String {
+        format!("{}/{}/day/{}", self.base_url, year, day)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Downloads the puzzle input of `day`
+    pub fn input(&self, year: u16, day: u8) -> AdventResult<String> {
+        let url = format!("{}/input", self.day_url(year, day));
+        debug!(%url, "Fetching input");
+        let response = self.backend.get(&url, &[("Cookie", &self.cookie())])?;
+        Self::check_status(&url, year, day, response)
+    }
+
+    /// Submits `answer` to `part` of `day`
+    pub fn submit(&self, year: u16, day: u8, part: Part, answer: &str) -> AdventResult<Verdict> {
+        let url = format!("{}/answer", self.day_url(year, day));
+        let level = match part {
+            Part::A => "1",
+            Part::B => "2",
+        };
+        debug!(%url, level, answer, "Submitting answer");
+        let response = self.backend.post_form(
+            &url,
+            &[("Cookie", &self.cookie())],
+            &[("level", level), ("answer", answer)],
+        )?;
+        let page = Self::check_status(&url, year, day, response)?;
+        Verdict::from_page(&page).ok_or_else(|| {
+            AdventErrors::RequestError(format!("could not find the verdict in the page of {}", url))
+        })
+    }
+
+    /// Body of a successful response, or an error explaining the failure
+    fn check_status(url: &str, year: u16, day: u8, response: HttpResponse) -> AdventResult<String> {
+        match response.status {
+            200 => Ok(response.body),
+            400 | 401 | 403 => Err(AdventErrors::RequestError(
+                "the session token was refused, it may have expired".to_string(),
+            )),
+            404 => Err(AdventErrors::RequestError(format!(
+                "day {} of {} is not unlocked yet",
+                day, year
+            ))),
+            status => Err(AdventErrors::RequestError(format!(
+                "{} answered {}: {}",
+                url,
+                status,
+                response.body.lines().next().unwrap_or_default()
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// A request received by the stand-in server
+    #[derive(Debug)]
+    pub(crate) struct Received {
+        pub request_line: String,
+        pub headers: Vec<String>,
+        pub body: String,
+    }
+
+    impl Received {
+        pub fn has_header(&self, header: &str) -> bool {
+            self.headers
+                .iter()
+                .any(|line| line.eq_ignore_ascii_case(header))
+        }
+    }
+
+    /// Starts a local stand-in for the website, answering one request per response in order
+    ///
+    /// # Return
+    /// The server's URL, and a handle returning the requests once every response is sent
+    pub(crate) fn serve(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, JoinHandle<Vec<Received>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Should bind a local port");
+        let url = format!("http://{}", listener.local_addr().expect("Bound"));
+        let handle = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, reply)| {
+                    let (mut stream, _) = listener.accept().expect("Should accept");
+                    let mut reader = BufReader::new(stream.try_clone().expect("Should clone"));
+                    let mut lines = vec![];
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).expect("Should read");
+                        match line.trim_end() {
+                            "" => break,
+                            line => lines.push(line.to_string()),
+                        }
+                    }
+                    let length = lines
+                        .iter()
+                        .find_map(|line| {
+                            let (name, value) = line.split_once(':')?;
+                            name.eq_ignore_ascii_case("content-length")
+                                .then(|| value.trim().parse::<usize>().ok())?
+                        })
+                        .unwrap_or(0);
+                    let mut body = vec![0; length];
+                    reader.read_exact(&mut body).expect("Should read body");
+
+                    write!(
+                        stream,
+                        "HTTP/1.1 {} Stand-in\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        reply.len(),
+                        reply
+                    )
+                    .expect("Should respond");
+                    Received {
+                        request_line: lines.remove(0),
+                        headers: lines,
+                        body: String::from_utf8(body).expect("UTF-8 body"),
+                    }
+                })
+                .collect()
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_input() {
+        let (url, server) = serve(vec![(200, "1000\n2000\n")]);
+        let client = AdventClient::new(UreqBackend::default(), url, "abc123");
+
+        assert_eq!(client.input(2022, 1).expect("Should fetch"), "1000\n2000\n");
+        let received = server.join().expect("Server should finish");
+        assert_eq!(received[0].request_line, "GET /2022/day/1/input HTTP/1.1");
+        assert!(
+            received[0].has_header("Cookie: session=abc123"),
+            "{:?}",
+            received
+        );
+        assert!(received[0].body.is_empty());
+    }
+
+    #[test]
+    fn test_input_errors() {
+        let (url, server) = serve(vec![(400, "Please log in"), (404, ""), (500, "Oops\nMore")]);
+        let client = AdventClient::new(UreqBackend::default(), url, "expired");
+
+        for expected in ["session token", "not unlocked", "answered 500: Oops"] {
+            match client.input(2022, 25) {
+                Err(AdventErrors::RequestError(msg)) => assert!(msg.contains(expected), "{}", msg),
+                other => panic!("Expected RequestError, got {:?}", other),
+            }
+        }
+        server.join().expect("Server should finish");
+    }
+
+    #[test]
+    fn test_verdict_from_page() {
+        let page = |article: &str| {
+            format!(
+                "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+                article
+            )
+        };
+
+        let right = page("That's the right answer!  You are <em>one gold star</em> closer.");
+        assert_eq!(Verdict::from_page(&right), Some(Verdict::Correct));
+        let high = page("That&#39;s not the right answer; your answer is too high.  Please wait one minute before trying again.");
+        assert_eq!(Verdict::from_page(&high), Some(Verdict::TooHigh));
+        let low = page("That's not the right answer; your answer is too low.");
+        assert_eq!(Verdict::from_page(&low), Some(Verdict::TooLow));
+        let wrong = page("That's not the right answer.  If you're stuck, make sure you're using the full input data.");
+        assert_eq!(Verdict::from_page(&wrong), Some(Verdict::Incorrect));
+        let level =
+            page("You don't seem to be solving the right level.  Did you already complete it?");
+        assert_eq!(Verdict::from_page(&level), Some(Verdict::WrongLevel));
+        assert_eq!(Verdict::from_page("<html>Log in</html>"), None);
+    }
+
+    #[test]
+    fn test_rate_limited_wait() {
+        let page = "<main><article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 38s left to wait. <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article></main>";
+        assert_eq!(
+            Verdict::from_page(page),
+            Some(Verdict::RateLimited {
+                wait: Duration::from_secs(4 * 60 + 38)
+            })
+        );
+        assert_eq!(parse_wait("1h 2m 3s"), Some(Duration::from_secs(3723)));
+        assert_eq!(parse_wait("soon"), None);
+    }
+
+    #[test]
+    fn test_submit() {
+        let (url, server) = serve(vec![
+            (
+                200,
+                "<main><article><p>That's the right answer!</p></article></main>",
+            ),
+            (200, "<main>Advent of Code</main>"),
+        ]);
+        let client = AdventClient::new(UreqBackend::default(), url, "abc123");
+
+        let verdict = client
+            .submit(2022, 3, Part::B, "70")
+            .expect("Should submit");
+        assert_eq!(verdict, Verdict::Correct);
+        match client.submit(2022, 3, Part::B, "70") {
+            Err(AdventErrors::RequestError(msg)) => assert!(msg.contains("verdict"), "{}", msg),
+            other => panic!("Expected RequestError, got {:?}", other),
+        }
+
+        let received = server.join().expect("Server should finish");
+        assert_eq!(received[0].request_line, "POST /2022/day/3/answer HTTP/1.1");
+        assert!(received[0].has_header("Cookie: session=abc123"));
+        assert_eq!(received[0].body, "level=2&answer=70");
+    }
+}
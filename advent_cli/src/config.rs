@@ -0,0 +1,173 @@
+//! Effective settings of the CLI: the configuration files overridden by command line flags
+
+use clap::{ArgAction, Args, Subcommand};
+use serde::Serialize;
+use std::path::PathBuf;
+
+use advent_common::{
+    config::{AdventConfig, OutputFormat, Verbosity},
+    errors::{AdventErrors, AdventResult},
+    registry,
+};
+
+/// Default number of timed runs of the `bench` command
+pub const DEFAULT_BENCH_ITERATIONS: u32 = 100;
+/// Default number of untimed runs of the `bench` command
+pub const DEFAULT_BENCH_WARMUP: u32 = 5;
+/// Year of the puzzles when none is configured and no solution is registered
+pub const DEFAULT_YEAR: u16 = 2022;
+
+/// Flags accepted by every command, overriding the configuration files
+#[derive(Args, Clone, Debug, Default)]
+pub struct GlobalArgs {
+    /// Only run solutions of this year
+    #[arg(long, global = true)]
+    year: Option<u16>,
+    /// How to print the results
+    #[arg(long, global = true)]
+    format: Option<OutputFormat>,
+    /// Log more to stderr: `-v` for progress, `-vv` for debugging, `-vvv` for everything
+    #[arg(short, long, action = ArgAction::Count, global = true, conflicts_with = "quiet")]
+    verbose: u8,
+    /// Only log errors
+    #[arg(short, long, global = true)]
+    quiet: bool,
+}
+
+impl GlobalArgs {
+    /// Verbosity asked for with `-v` or `-q`, if any
+    fn verbosity(&self) -> Option<Verbosity> {
+        match (self.quiet, self.verbose) {
+            (true, _) => Some(Verbosity::Quiet),
+            (false, 0) => None,
+            (false, 1) => Some(Verbosity::Verbose),
+            (false, 2) => Some(Verbosity::Debug),
+            (false, _) => Some(Verbosity::Trace),
+        }
+    }
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum ConfigCommands {
+    /// Print the effective configuration, after merging every file and flag
+    Show,
+}
+
+/// Arguments of the `config` command
+#[derive(Args, Clone, Debug)]
+pub struct ConfigArgs {
+    #[clap(subcommand)]
+    command: ConfigCommands,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct BenchSettings {
+    pub iterations: u32,
+    pub warmup: u32,
+}
+
+/// Every setting with its final value
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct Settings {
+    pub input_dir: Option<PathBuf>,
+    pub year: Option<u16>,
+    pub format: OutputFormat,
+    pub verbosity: Verbosity,
+    pub bench: BenchSettings,
+}
+
+impl Settings {
+    /// Fills the unset values of `config` with defaults, then applies the command line flags
+    pub fn resolve(config: AdventConfig, args: &GlobalArgs) -> Self {
+        Self {
+            input_dir: config.input_dir,
+            year: args.year.or(config.year),
+            format: args.format.or(config.format).unwrap_or_default(),
+            verbosity: args.verbosity().or(config.verbosity).unwrap_or_default(),
+            bench: BenchSettings {
+                iterations: config.bench.iterations.unwrap_or(DEFAULT_BENCH_ITERATIONS),
+                warmup: config.bench.warmup.unwrap_or(DEFAULT_BENCH_WARMUP),
+            },
+        }
+    }
+
+    /// Loads the configuration files and applies the command line flags
+    pub fn load(args: &GlobalArgs) -> AdventResult<Self> {
+        Ok(Self::resolve(AdventConfig::load()?, args))
+    }
+
+    /// The configured year, or else the latest year with a registered solution
+    pub fn year_or_latest(&self) -> u16 {
+        self.year
+            .or_else(|| registry::solutions().iter().map(|entry| entry.year).max())
+            .unwrap_or(DEFAULT_YEAR)
+    }
+
+    /// The settings in the configuration file format
+    pub fn to_toml(&self) -> AdventResult<String> {
+        toml::to_string(self).map_err(|err| AdventErrors::ConfigError(err.to_string()))
+    }
+}
+
+/// Runs the `config` command
+pub(crate) fn run_config(args: &ConfigArgs, global: &GlobalArgs) -> AdventResult<()> {
+    match args.command {
+        ConfigCommands::Show => {
+            let files = AdventConfig::load_files()?;
+            if files.is_empty() {
+                println!("# No configuration file found");
+            }
+            for (path, _) in files.iter() {
+                println!("# Loaded {}", path.display());
+            }
+
+            let config = files
+                .into_iter()
+                .fold(AdventConfig::default(), |config, (_, file)| {
+                    config.merge(file)
+                });
+            print!("{}", Settings::resolve(config, global).to_toml()?);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_flags_override_config() {
+        let config = AdventConfig {
+            year: Some(2021),
+            ..AdventConfig::default()
+        };
+
+        let from_file = Settings::resolve(config.clone(), &GlobalArgs::default());
+        assert_eq!(from_file.year, Some(2021));
+        assert_eq!(from_file.bench.iterations, DEFAULT_BENCH_ITERATIONS);
+
+        let flagged = Settings::resolve(
+            config,
+            &GlobalArgs {
+                year: Some(2022),
+                format: Some(OutputFormat::Json),
+                verbose: 2,
+                quiet: false,
+            },
+        );
+        assert_eq!(flagged.year, Some(2022));
+        assert_eq!(flagged.format, OutputFormat::Json);
+        assert_eq!(flagged.verbosity, Verbosity::Debug);
+    }
+
+    #[test]
+    fn test_show_as_toml() {
+        let settings = Settings::resolve(AdventConfig::default(), &GlobalArgs::default());
+        let shown = settings.to_toml().expect("Serializable");
+        assert!(shown.contains("format = \"text\""), "{}", shown);
+        assert!(shown.contains("[bench]\niterations = 100"), "{}", shown);
+        assert!(!shown.contains("year"), "{}", shown);
+    }
+}
@@ -0,0 +1,153 @@
+//! Downloads puzzle inputs to where the solutions look for them
+
+use clap::Args;
+use std::{fs, path::PathBuf};
+
+use advent_common::{
+    errors::{AdventErrors, AdventResult},
+    input::{InputLocator, InputSource, DEFAULT_INPUT_FILE},
+    registry,
+};
+
+use crate::{
+    client::{AdventClient, HttpBackend},
+    config::Settings,
+};
+
+/// Arguments of the `fetch` command
+#[derive(Args, Clone, Debug)]
+pub struct FetchArgs {
+    /// Day whose input is downloaded
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+}
+
+/// What `fetch_input` did
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was downloaded to this file
+    Downloaded(PathBuf),
+    /// The input was already in this file, so nothing was requested
+    AlreadyPresent(PathBuf),
+}
+
+/// Downloads the input of `day` to the first location `locator` searches,
+/// unless the input can already be found.
+/// Empty files, such as the placeholder created by `new`, are not inputs.
+pub fn fetch_input<B: HttpBackend>(
+    client: &AdventClient<B>,
+    locator: &InputLocator,
+    year: u16,
+    day: u8,
+) -> AdventResult<Fetched> {
+    if let Ok(InputSource::File(path)) = locator.locate() {
+        if fs::metadata(&path)?.len() > 0 {
+            return Ok(Fetched::AlreadyPresent(path));
+        }
+    }
+    let destination = locator.destination().ok_or_else(|| {
+        AdventErrors::UsageError("There is nowhere to save the input".to_string())
+    })?;
+
+    let input = client.input(year, day)?;
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&destination, input)?;
+    Ok(Fetched::Downloaded(destination))
+}
+
+/// Runs the `fetch` command.
+/// The input is saved where the day's solution looks for it, or in `./dayN/` for days without one.
+pub(crate) fn run_fetch(args: &FetchArgs, settings: &Settings) -> AdventResult<()> {
+    let year = settings.year_or_latest();
+    let entry = registry::solutions()
+        .into_iter()
+        .find(|entry| entry.year == year && entry.day == args.day);
+    let locator = match entry {
+        Some(entry) => entry.locator(None, settings.input_dir.clone()),
+        None => {
+            InputLocator::new(args.day, DEFAULT_INPUT_FILE).config_dir(settings.input_dir.clone())
+        }
+    };
+
+    match fetch_input(&AdventClient::from_env()?, &locator, year, args.day)? {
+        Fetched::Downloaded(path) => {
+            println!("Saved the input of day {} to {}", args.day, path.display())
+        }
+        Fetched::AlreadyPresent(path) => println!(
+            "The input of day {} is already in {}, not downloading it again",
+            args.day,
+            path.display()
+        ),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::client::HttpResponse;
+    use std::cell::RefCell;
+
+    /// Answers every request with the same input and records the URLs
+    #[derive(Default)]
+    struct CannedBackend {
+        requested: RefCell<Vec<String>>,
+    }
+
+    impl HttpBackend for CannedBackend {
+        fn get(&self, url: &str, _headers: &[(&str, &str)]) -> AdventResult<HttpResponse> {
+            self.requested.borrow_mut().push(url.to_string());
+            Ok(HttpResponse {
+                status: 200,
+                body: "A Y\nB X\n".to_string(),
+            })
+        }
+
+        fn post_form(
+            &self,
+            _url: &str,
+            _headers: &[(&str, &str)],
+            _form: &[(&str, &str)],
+        ) -> AdventResult<HttpResponse> {
+            unreachable!("Fetching never posts")
+        }
+    }
+
+    #[test]
+    fn test_fetch_once() {
+        let dir = tempfile::tempdir().expect("Should create temp dir");
+        let path = dir.path().join("inputs/day2.txt");
+        let locator = InputLocator::new(2, DEFAULT_INPUT_FILE)
+            .explicit(Some(InputSource::File(path.clone())));
+        let client = AdventClient::new(CannedBackend::default(), "http://aoc.test/", "token");
+
+        let fetched = fetch_input(&client, &locator, 2022, 2).expect("Should fetch");
+        assert_eq!(fetched, Fetched::Downloaded(path.clone()));
+        assert_eq!(fs::read_to_string(&path).expect("Saved"), "A Y\nB X\n");
+
+        let again = fetch_input(&client, &locator, 2022, 2).expect("Should not fetch");
+        assert_eq!(again, Fetched::AlreadyPresent(path));
+        assert_eq!(
+            *client.backend().requested.borrow(),
+            vec!["http://aoc.test/2022/day/2/input"]
+        );
+    }
+
+    #[test]
+    fn test_fetch_replaces_placeholder() {
+        let dir = tempfile::tempdir().expect("Should create temp dir");
+        let path = dir.path().join("input.txt");
+        fs::write(&path, "").expect("Should write");
+        let locator = InputLocator::new(2, DEFAULT_INPUT_FILE)
+            .explicit(Some(InputSource::File(path.clone())));
+        let client = AdventClient::new(CannedBackend::default(), "http://aoc.test", "token");
+
+        assert_eq!(
+            fetch_input(&client, &locator, 2022, 2).expect("Should fetch"),
+            Fetched::Downloaded(path)
+        );
+    }
+}
@@ -1 +1,10 @@
+pub mod bench;
 pub mod cli;
+pub mod client;
+pub mod config;
+pub mod fetch;
+pub mod output;
+pub mod scaffold;
+pub mod submit;
+pub mod summary;
+pub mod verify;
@@ -1,5 +1,7 @@
+use std::process::ExitCode;
+
 use advent_cli::cli::run_cli;
 
-pub fn main() {
-    run_cli();
+pub fn main() -> ExitCode {
+    run_cli()
 }
@@ -0,0 +1,208 @@
+//! Formats answers for display, and solution results for scripts
+
+use serde::Serialize;
+use std::fmt;
+
+use advent_common::{
+    command::{Answer, AnswerValue},
+    errors::{AdventErrors, AdventResult},
+};
+
+use crate::summary::RunOutcome;
+
+/// Formats an answer as `<description>: <value>`, putting art on its own lines
+pub fn format_answer(answer: &Answer) -> String {
+    let value = match &answer.value {
+        AnswerValue::Art(art) => format!("\n{}", art),
+        value => value.to_string(),
+    };
+    match &answer.description {
+        Some(description) => format!("{}: {}", description, value),
+        None => value.trim_start().to_string(),
+    }
+}
+
+/// Formats the answer's value on a single line, for use in tables
+pub fn format_answer_inline(answer: &Answer) -> String {
+    match &answer.value {
+        AnswerValue::Art(art) => art.lines().collect::<Vec<&str>>().join(" / "),
+        value => value.to_string(),
+    }
+}
+
+/// An answer's value in an `OutcomeRecord`, so integers stay numbers in JSON
+#[derive(Clone, Debug, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum RecordAnswer {
+    Integer(i128),
+    /// Text answers, and pictures with their lines kept
+    Text(String),
+}
+
+impl From<&AnswerValue> for RecordAnswer {
+    fn from(value: &AnswerValue) -> Self {
+        match value {
+            AnswerValue::Integer(value) => RecordAnswer::Integer(*value),
+            AnswerValue::Text(value) | AnswerValue::Art(value) => RecordAnswer::Text(value.clone()),
+        }
+    }
+}
+
+impl fmt::Display for RecordAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordAnswer::Integer(value) => write!(f, "{}", value),
+            RecordAnswer::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// Result of one solution in a machine-readable shape
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct OutcomeRecord {
+    pub year: u16,
+    pub day: u8,
+    pub part: String,
+    /// The answer's value, `None` when the solution failed
+    pub answer: Option<RecordAnswer>,
+    /// Time taken in milliseconds, including reading and parsing the input
+    pub elapsed_ms: f64,
+    /// Why the solution failed, `None` when it succeeded
+    pub error: Option<String>,
+}
+
+impl OutcomeRecord {
+    const CSV_HEADER: &'static str = "year,day,part,answer,elapsed_ms,error";
+
+    pub fn new(outcome: &RunOutcome) -> Self {
+        let (answer, error) = match &outcome.result {
+            Ok(answer) => (Some(RecordAnswer::from(&answer.value)), None),
+            Err(err) => (None, Some(err.to_string())),
+        };
+        Self {
+            year: outcome.entry.year,
+            day: outcome.entry.day,
+            part: outcome.entry.part.to_string(),
+            answer,
+            elapsed_ms: outcome.elapsed.as_secs_f64() * 1000.0,
+            error,
+        }
+    }
+
+    fn csv_row(&self) -> String {
+        [
+            self.year.to_string(),
+            self.day.to_string(),
+            self.part.clone(),
+            self.answer
+                .as_ref()
+                .map_or_else(String::new, RecordAnswer::to_string),
+            format!("{:.3}", self.elapsed_ms),
+            self.error.clone().unwrap_or_default(),
+        ]
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<String>>()
+        .join(",")
+    }
+}
+
+/// Quotes a CSV field when it holds a separator, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Every outcome as a JSON array of `OutcomeRecord`s
+pub fn format_json(outcomes: &[RunOutcome]) -> AdventResult<String> {
+    let records: Vec<OutcomeRecord> = outcomes.iter().map(OutcomeRecord::new).collect();
+    serde_json::to_string_pretty(&records).map_err(|err| AdventErrors::AdventError(err.to_string()))
+}
+
+/// Every outcome as a CSV line of `OutcomeRecord` fields, after a header line
+pub fn format_csv(outcomes: &[RunOutcome]) -> String {
+    let mut lines = vec![OutcomeRecord::CSV_HEADER.to_string()];
+    lines.extend(
+        outcomes
+            .iter()
+            .map(|outcome| OutcomeRecord::new(outcome).csv_row()),
+    );
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::summary::tests::DAY_ONE;
+    use std::time::Duration;
+
+    #[test]
+    fn test_format_answer() {
+        let answer = Answer::integer(157).with_description("Total Priority");
+        assert_eq!(format_answer(&answer), "Total Priority: 157");
+        assert_eq!(format_answer_inline(&answer), "157");
+
+        assert_eq!(format_answer(&Answer::text("CMZ")), "CMZ");
+
+        let art = Answer::art("#.\n.#").with_description("Letters");
+        assert_eq!(format_answer(&art), "Letters: \n#.\n.#");
+        assert_eq!(format_answer(&Answer::art("#.\n.#")), "#.\n.#");
+        assert_eq!(format_answer_inline(&art), "#. / .#");
+    }
+
+    fn outcomes() -> Vec<RunOutcome> {
+        vec![
+            RunOutcome {
+                entry: &DAY_ONE,
+                result: Ok(Answer::text("CMZ")),
+                elapsed: Duration::from_micros(1500),
+            },
+            RunOutcome {
+                entry: &DAY_ONE,
+                result: Err(AdventErrors::ConfigError("a \"b\", c".to_string())),
+                elapsed: Duration::from_millis(2),
+            },
+            RunOutcome {
+                entry: &DAY_ONE,
+                result: Ok(Answer::integer(68923)),
+                elapsed: Duration::from_millis(3),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_format_json() {
+        let json = format_json(&outcomes()).expect("Serializable");
+        let records: serde_json::Value = serde_json::from_str(&json).expect("Valid JSON");
+        assert_eq!(records[0]["year"], 2022);
+        assert_eq!(records[0]["part"], "a");
+        assert_eq!(records[0]["answer"], "CMZ");
+        assert!(records[0]["answer"].is_string());
+        assert_eq!(records[0]["elapsed_ms"], 1.5);
+        assert!(records[0]["error"].is_null());
+        assert!(records[1]["answer"].is_null());
+        assert_eq!(
+            records[1]["error"],
+            "Invalid configuration file: a \"b\", c"
+        );
+        assert_eq!(records[2]["answer"], 68923);
+        assert!(records[2]["answer"].is_i64());
+    }
+
+    #[test]
+    fn test_format_csv() {
+        let csv = format_csv(&outcomes());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "year,day,part,answer,elapsed_ms,error");
+        assert_eq!(lines[1], "2022,1,a,CMZ,1.500,");
+        assert_eq!(
+            lines[2],
+            "2022,1,a,,2.000,\"Invalid configuration file: a \"\"b\"\", c\""
+        );
+        assert_eq!(lines[3], "2022,1,a,68923,3.000,");
+    }
+}
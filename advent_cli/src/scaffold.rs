@@ -0,0 +1,314 @@
+//! Generates the crate of a new day from `advent_cli/templates/day` and registers it
+//! with the workspace, the CLI's dependencies and the CLI's list of linked days
+
+use clap::Args;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use advent_common::errors::{AdventErrors, AdventResult};
+
+use crate::config::{Settings, DEFAULT_YEAR};
+
+/// Replaced by the day number in the templates
+const DAY_PLACEHOLDER: &str = "__DAY__";
+/// Replaced by the year in the templates
+const YEAR_PLACEHOLDER: &str = "__YEAR__";
+
+/// Files of a new day, relative to its crate, and the templates they are created from
+const DAY_FILES: [(&str, &str); 7] = [
+    (
+        "Cargo.toml",
+        include_str!("../templates/day/Cargo.toml.tmpl"),
+    ),
+    ("src/lib.rs", include_str!("../templates/day/lib.rs.tmpl")),
+    (
+        "src/solution.rs",
+        include_str!("../templates/day/solution.rs.tmpl"),
+    ),
+    (
+        "benches/solution.rs",
+        include_str!("../templates/day/bench.rs.tmpl"),
+    ),
+    ("examples/example.txt", ""),
+    (
+        "examples/example.toml",
+        include_str!("../templates/day/example.toml.tmpl"),
+    ),
+    ("input.txt", ""),
+];
+
+/// Lines naming every day in files of the workspace, so a new day is picked up.
+/// Each entry is the file, relative to the workspace, and the template of its lines.
+const REGISTRATIONS: [(&str, &str); 3] = [
+    ("Cargo.toml", "    \"day__DAY__\","),
+    (
+        "advent_cli/Cargo.toml",
+        "advent_day__DAY__ = { path = \"../day__DAY__\" }",
+    ),
+    ("advent_cli/src/cli.rs", "use advent_day__DAY__ as _;"),
+];
+
+/// Arguments of the `new` command
+#[derive(Args, Clone, Debug)]
+pub struct NewArgs {
+    /// Day to create
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+}
+
+/// Fills the placeholders of `template`
+fn render(template: &str, day: u8, year: u16) -> String {
+    template
+        .replace(DAY_PLACEHOLDER, &day.to_string())
+        .replace(YEAR_PLACEHOLDER, &year.to_string())
+}
+
+/// Adds the line of `day` to `contents`, next to the lines of the other days and in order.
+///
+/// # Param
+/// * `path` - File of `contents`, for the errors
+/// * `line_template` - Template of the lines, one per day
+///
+/// # Return
+/// The new contents, or an error if the day is already listed or no day is listed
+fn register_line(
+    path: &Path,
+    contents: &str,
+    line_template: &str,
+    day: u8,
+) -> AdventResult<String> {
+    let day_line = |day: u8| render(line_template, day, DEFAULT_YEAR);
+    let new_line = day_line(day);
+    let mut lines: Vec<String> = contents.lines().map(str::to_string).collect();
+    if lines.contains(&new_line) {
+        return Err(AdventErrors::ScaffoldError(format!(
+            "{}: day {} is already registered (`{}`)",
+            path.display(),
+            day,
+            new_line.trim()
+        )));
+    }
+
+    let listed: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, line)| Some((idx, (1..=25).find(|day| day_line(*day) == *line)?)))
+        .collect();
+    let first = listed.first().ok_or_else(|| {
+        AdventErrors::ScaffoldError(format!(
+            "{}: no line like `{}` to add to",
+            path.display(),
+            day_line(1).trim()
+        ))
+    })?;
+    let position = listed
+        .iter()
+        .rfind(|(_, listed_day)| *listed_day < day)
+        .map_or(first.0, |(idx, _)| idx + 1);
+    lines.insert(position, new_line);
+
+    let mut registered = lines.join("\n");
+    if contents.ends_with('\n') {
+        registered.push('\n');
+    }
+    Ok(registered)
+}
+
+/// Creates the crate of `day` in the workspace at `root` and registers it.
+/// Nothing is written unless every file can be registered.
+///
+/// # Return
+/// The files created or changed
+pub fn scaffold_day(root: &Path, day: u8, year: u16) -> AdventResult<Vec<PathBuf>> {
+    let day_dir = root.join(format!("day{}", day));
+    if day_dir.exists() {
+        return Err(AdventErrors::ScaffoldError(format!(
+            "{} already exists",
+            day_dir.display()
+        )));
+    }
+
+    let registrations = REGISTRATIONS
+        .iter()
+        .map(|(file, line_template)| {
+            let path = root.join(file);
+            let contents = fs::read_to_string(&path).map_err(|err| {
+                AdventErrors::ScaffoldError(format!("{}: {}", path.display(), err))
+            })?;
+            let registered = register_line(&path, &contents, line_template, day)?;
+            Ok((path, registered))
+        })
+        .collect::<AdventResult<Vec<_>>>()?;
+
+    let mut written = Vec::new();
+    for (file, template) in DAY_FILES {
+        let path = day_dir.join(file);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, render(template, day, year))?;
+        written.push(path);
+    }
+    for (path, registered) in registrations {
+        fs::write(&path, registered)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// The closest directory above `start` whose `Cargo.toml` declares the workspace
+fn find_workspace_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| {
+            fs::read_to_string(dir.join("Cargo.toml"))
+                .is_ok_and(|manifest| manifest.lines().any(|line| line.trim() == "[workspace]"))
+        })
+        .map(Path::to_path_buf)
+}
+
+/// Runs the `new` command.
+/// The day belongs to the configured year, or else to the latest year with a solution.
+pub(crate) fn run_new(args: &NewArgs, settings: &Settings) -> AdventResult<()> {
+    let cwd = env::current_dir()?;
+    let root = find_workspace_root(&cwd).ok_or_else(|| {
+        AdventErrors::ScaffoldError(format!(
+            "no workspace found above {}, run this from the repository",
+            cwd.display()
+        ))
+    })?;
+    for path in scaffold_day(&root, args.day, settings.year_or_latest())? {
+        println!(
+            "Wrote {}",
+            path.strip_prefix(&root).unwrap_or(&path).display()
+        );
+    }
+    println!(
+        "Add the examples to day{}/examples and the puzzle input to day{}/input.txt",
+        args.day, args.day
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    /// Workspace at `root` with days 1 and 3
+    fn fake_workspace(root: &Path) {
+        fs::create_dir_all(root.join("advent_cli/src")).expect("Should create dirs");
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\n\nmembers = [\n    \"advent_cli\",\n    \"day1\",\n    \"day3\",\n]\n",
+        )
+        .expect("Should write");
+        fs::write(
+            root.join("advent_cli/Cargo.toml"),
+            "[dependencies]\nadvent_day1 = { path = \"../day1\" }\nadvent_day3 = { path = \"../day3\" }\nserde = \"1.0\"\n",
+        )
+        .expect("Should write");
+        fs::write(
+            root.join("advent_cli/src/cli.rs"),
+            "use advent_day1 as _;\nuse advent_day3 as _;\n\nfn main() {}\n",
+        )
+        .expect("Should write");
+    }
+
+    #[test]
+    fn test_register_line_in_order() {
+        let path = Path::new("cli.rs");
+        let template = "use advent_day__DAY__ as _;";
+        let contents = "use advent_day2 as _;\nuse advent_day4 as _;\n";
+
+        let middle = register_line(path, contents, template, 3).expect("Should register");
+        assert_eq!(
+            middle,
+            "use advent_day2 as _;\nuse advent_day3 as _;\nuse advent_day4 as _;\n"
+        );
+        let first = register_line(path, contents, template, 1).expect("Should register");
+        assert!(
+            first.starts_with("use advent_day1 as _;\nuse advent_day2"),
+            "{}",
+            first
+        );
+        let last = register_line(path, contents, template, 12).expect("Should register");
+        assert!(
+            last.ends_with("day4 as _;\nuse advent_day12 as _;\n"),
+            "{}",
+            last
+        );
+
+        assert!(register_line(path, contents, template, 2).is_err());
+        assert!(register_line(path, "fn main() {}", template, 2).is_err());
+    }
+
+    #[test]
+    fn test_scaffold_day() {
+        let dir = tempfile::tempdir().expect("Should create temp dir");
+        fake_workspace(dir.path());
+
+        let written = scaffold_day(dir.path(), 2, 2022).expect("Should scaffold");
+        assert!(written.contains(&dir.path().join("day2/src/solution.rs")));
+
+        let solution =
+            fs::read_to_string(dir.path().join("day2/src/solution.rs")).expect("Should exist");
+        assert!(solution.contains("pub struct Day2 {}"), "{}", solution);
+        assert!(
+            solution.contains("register_solution!(Day2, year = 2022, day = 2);"),
+            "{}",
+            solution
+        );
+        assert!(!solution.contains(DAY_PLACEHOLDER));
+        assert!(dir.path().join("day2/examples/example.toml").is_file());
+
+        let members = fs::read_to_string(dir.path().join("Cargo.toml")).expect("Should exist");
+        assert!(
+            members.contains("\"day1\",\n    \"day2\",\n    \"day3\","),
+            "{}",
+            members
+        );
+        let cli =
+            fs::read_to_string(dir.path().join("advent_cli/src/cli.rs")).expect("Should exist");
+        assert!(
+            cli.contains("day1 as _;\nuse advent_day2 as _;\nuse advent_day3"),
+            "{}",
+            cli
+        );
+    }
+
+    #[test]
+    fn test_refuses_existing_day() {
+        let dir = tempfile::tempdir().expect("Should create temp dir");
+        fake_workspace(dir.path());
+        let manifest_before =
+            fs::read_to_string(dir.path().join("Cargo.toml")).expect("Should exist");
+
+        fs::create_dir(dir.path().join("day3")).expect("Should create dir");
+        assert!(matches!(
+            scaffold_day(dir.path(), 3, 2022),
+            Err(AdventErrors::ScaffoldError(_))
+        ));
+
+        // Registered without a crate: nothing is written
+        fs::remove_dir(dir.path().join("day3")).expect("Should remove dir");
+        assert!(scaffold_day(dir.path(), 3, 2022).is_err());
+        assert!(!dir.path().join("day3").exists());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("Cargo.toml")).expect("Should exist"),
+            manifest_before
+        );
+    }
+
+    #[test]
+    fn test_find_workspace_root() {
+        let dir = tempfile::tempdir().expect("Should create temp dir");
+        fake_workspace(dir.path());
+        assert_eq!(
+            find_workspace_root(&dir.path().join("advent_cli/src")),
+            Some(dir.path().to_path_buf())
+        );
+    }
+}
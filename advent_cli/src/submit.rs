@@ -0,0 +1,273 @@
+//! Submits answers to the website and records the accepted ones in the known-answer store
+
+use clap::Args;
+use std::path::PathBuf;
+
+use advent_common::{
+    answers::AnswerStore,
+    command::AnswerValue,
+    errors::{AdventErrors, AdventResult},
+    input::InputSource,
+    registry::{DaySelection, Part},
+};
+
+use crate::{
+    cli::select_solutions,
+    client::{AdventClient, HttpBackend, Verdict},
+    config::Settings,
+    summary::RunOutcome,
+};
+
+/// Arguments of the `submit` command
+#[derive(Args, Clone, Debug)]
+pub struct SubmitArgs {
+    /// Day of the answer
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// Part of the answer (`a` or `b`)
+    part: Part,
+    /// Answer to submit instead of running the solution
+    #[arg(long)]
+    answer: Option<String>,
+    /// Path to the input file, or `-` to read stdin. Searched for when not given
+    #[arg(short, long, conflicts_with = "answer")]
+    input: Option<InputSource>,
+    /// Path to the answers file. Searched for from the current directory upwards when not given
+    #[arg(long)]
+    answers: Option<PathBuf>,
+    /// Submit even if the website already accepted an answer for the part
+    #[arg(long)]
+    force: bool,
+}
+
+/// Runs the solution of `part` of `day` and returns its answer in the form to submit
+fn solve(
+    settings: &Settings,
+    year: u16,
+    day: u8,
+    part: Part,
+    input: Option<InputSource>,
+) -> AdventResult<String> {
+    let days = DaySelection {
+        first: day,
+        last: day,
+    };
+    let selected = select_solutions(Some(year), Some(days), Some(part), input.as_ref())?;
+    let answer = RunOutcome::run_all(&selected, input, settings.input_dir.as_deref())
+        .remove(0)
+        .result?;
+    match answer.value {
+        AnswerValue::Art(art) => Err(AdventErrors::UsageError(format!(
+            "The answer is a picture, read it and pass it with --answer:\n{}",
+            art
+        ))),
+        value => Ok(value.to_string()),
+    }
+}
+
+/// Submits `answer` unless the website already accepted one for the part,
+/// and records it in `store` as accepted when it is correct.
+/// Answers recorded by `verify --record` were never confirmed, so do not prevent submitting.
+///
+/// # Param
+/// * `force` - Submit even if an accepted answer is recorded
+///
+/// # Return
+/// The verdict of the website, or an error if the part was already solved
+pub fn submit_answer<B: HttpBackend>(
+    client: &AdventClient<B>,
+    store: &mut AnswerStore,
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: &str,
+    force: bool,
+) -> AdventResult<Verdict> {
+    if let Some(accepted) = store.accepted(year, day, part) {
+        if !force {
+            return Err(AdventErrors::AlreadySolved(format!(
+                "day {}{} of {} was accepted with `{}`, not submitting `{}` without --force",
+                day, part, year, accepted, answer
+            )));
+        }
+    }
+
+    let verdict = client.submit(year, day, part, answer)?;
+    if verdict == Verdict::Correct {
+        store.record_accepted(year, day, part, answer);
+    }
+    Ok(verdict)
+}
+
+/// Runs the `submit` command.
+/// Fails unless the answer is accepted, so scripts can tell a wrong answer apart.
+pub(crate) fn run_submit(args: &SubmitArgs, settings: &Settings) -> AdventResult<()> {
+    let year = settings.year_or_latest();
+    let answer = match &args.answer {
+        Some(answer) => answer.trim().to_string(),
+        None => solve(settings, year, args.day, args.part, args.input.clone())?,
+    };
+    let answers_path = match &args.answers {
+        Some(path) => path.to_owned(),
+        None => AnswerStore::default_path()?,
+    };
+    let mut store = AnswerStore::load(&answers_path)?;
+
+    let client = AdventClient::from_env()?;
+    let verdict = submit_answer(
+        &client, &mut store, year, args.day, args.part, &answer, args.force,
+    )?;
+    check_verdict(&verdict, &answer, args.day, args.part)?;
+
+    store.save(&answers_path)?;
+    println!(
+        "`{}` is the right answer to day {}{}, saved to {}",
+        answer,
+        args.day,
+        args.part,
+        answers_path.display()
+    );
+    Ok(())
+}
+
+/// Turns every verdict but `Correct` into an error.
+/// Only verdicts where the website checked the answer are a `WrongAnswer`.
+fn check_verdict(verdict: &Verdict, answer: &str, day: u8, part: Part) -> AdventResult<()> {
+    match verdict {
+        Verdict::Correct => Ok(()),
+        Verdict::RateLimited { wait } => Err(AdventErrors::RequestError(format!(
+            "an answer was submitted too recently, wait {}s before submitting `{}`",
+            wait.as_secs(),
+            answer
+        ))),
+        Verdict::WrongLevel => Err(AdventErrors::AlreadySolved(format!(
+            "the website did not check `{}` as day {}{} is {}",
+            answer, day, part, verdict
+        ))),
+        Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect => {
+            Err(AdventErrors::WrongAnswer(format!(
+                "`{}` is not the right answer to day {}{}: {}",
+                answer, day, part, verdict
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::client::{tests::serve, UreqBackend};
+
+    #[test]
+    fn test_records_correct_answer() {
+        let (url, server) = serve(vec![
+            (200, "<main><article><p>That's not the right answer; your answer is too low.</p></article></main>"),
+            (200, "<main><article><p>That's the right answer!</p></article></main>"),
+        ]);
+        let client = AdventClient::new(UreqBackend::default(), url, "token");
+        let mut store = AnswerStore::default();
+
+        let low = submit_answer(&client, &mut store, 2022, 4, Part::A, "579", false)
+            .expect("Should submit");
+        assert_eq!(low, Verdict::TooLow);
+        assert_eq!(store.get(2022, 4, Part::A), None);
+
+        let right = submit_answer(&client, &mut store, 2022, 4, Part::A, "580", false)
+            .expect("Should submit");
+        assert_eq!(right, Verdict::Correct);
+        assert_eq!(store.get(2022, 4, Part::A), Some("580"));
+
+        let bodies: Vec<String> = server
+            .join()
+            .expect("Server should finish")
+            .into_iter()
+            .map(|received| received.body)
+            .collect();
+        assert_eq!(bodies, vec!["level=1&answer=579", "level=1&answer=580"]);
+    }
+
+    #[test]
+    fn test_rate_limited_is_not_recorded() {
+        let (url, server) = serve(vec![(
+            200,
+            "<main><article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 38s left to wait.</p></article></main>",
+        )]);
+        let client = AdventClient::new(UreqBackend::default(), url, "token");
+        let mut store = AnswerStore::default();
+
+        let verdict = submit_answer(&client, &mut store, 2022, 4, Part::B, "895", false)
+            .expect("Should submit");
+        assert_eq!(
+            verdict,
+            Verdict::RateLimited {
+                wait: std::time::Duration::from_secs(38)
+            }
+        );
+        assert_eq!(store, AnswerStore::default());
+        server.join().expect("Server should finish");
+    }
+
+    #[test]
+    fn test_wrong_level_is_not_a_wrong_answer() {
+        match check_verdict(&Verdict::WrongLevel, "70", 3, Part::B) {
+            Err(AdventErrors::AlreadySolved(msg)) => {
+                assert!(msg.contains("did not check"), "{}", msg)
+            }
+            other => panic!("Expected AlreadySolved, got {:?}", other),
+        }
+        let low = check_verdict(&Verdict::TooLow, "69", 3, Part::B);
+        assert!(
+            matches!(low, Err(AdventErrors::WrongAnswer(_))),
+            "{:?}",
+            low
+        );
+        let wait = Verdict::RateLimited {
+            wait: std::time::Duration::from_secs(38),
+        };
+        let limited = check_verdict(&wait, "70", 3, Part::B);
+        assert!(
+            matches!(limited, Err(AdventErrors::RequestError(_))),
+            "{:?}",
+            limited
+        );
+        assert!(check_verdict(&Verdict::Correct, "70", 3, Part::B).is_ok());
+    }
+
+    #[test]
+    fn test_submits_answer_from_verify_record() {
+        let (url, server) = serve(vec![(
+            200,
+            "<main><article><p>That's the right answer!</p></article></main>",
+        )]);
+        let client = AdventClient::new(UreqBackend::default(), url, "token");
+        let mut store = AnswerStore::default();
+        // What `verify --record` leaves behind: an answer the website never saw
+        store.record(2022, 1, Part::A, "68923");
+
+        let verdict = submit_answer(&client, &mut store, 2022, 1, Part::A, "68923", false)
+            .expect("Should submit");
+        assert_eq!(verdict, Verdict::Correct);
+        assert_eq!(store.accepted(2022, 1, Part::A), Some("68923"));
+        assert_eq!(server.join().expect("Server should finish").len(), 1);
+    }
+
+    #[test]
+    fn test_does_not_resubmit_solved_part() {
+        // Nothing listens here, so any request would fail with a RequestError
+        let client = AdventClient::new(UreqBackend::default(), "http://127.0.0.1:9", "token");
+        let mut store = AnswerStore::default();
+        store.record_accepted(2022, 1, Part::A, "68923");
+
+        match submit_answer(&client, &mut store, 2022, 1, Part::A, "68923", false) {
+            Err(err @ AdventErrors::AlreadySolved(_)) => {
+                assert!(err.to_string().contains("--force"), "{}", err)
+            }
+            other => panic!("Expected AlreadySolved, got {:?}", other),
+        }
+        match submit_answer(&client, &mut store, 2022, 1, Part::A, "68923", true) {
+            Err(AdventErrors::RequestError(_)) => {}
+            other => panic!("Forced submit should reach the website, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,316 @@
+//! Summary table printed after running several solutions
+
+use std::{
+    fmt,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use advent_common::{
+    command::Answer,
+    diagnostics::render_diagnostic,
+    errors::{AdventErrors, AdventResult},
+    input::InputSource,
+    registry::{ParsedInput, SolutionEntry},
+    tracing::{debug, info},
+};
+
+use crate::output::format_answer_inline;
+
+/// Result of running one solution
+#[derive(Debug)]
+pub struct RunOutcome {
+    pub entry: &'static SolutionEntry,
+    pub result: AdventResult<Answer>,
+    pub elapsed: Duration,
+}
+
+/// Reads and parses a solution's input, timing both
+fn prepare_input(
+    entry: &SolutionEntry,
+    input: Option<InputSource>,
+    input_dir: Option<&Path>,
+) -> (AdventResult<ParsedInput>, Duration) {
+    let start = Instant::now();
+    let parsed = entry
+        .locator(input, input_dir.map(Path::to_path_buf))
+        .locate()
+        .and_then(|source| {
+            info!(day = entry.day, "Reading input from {}", source);
+            let input = source.read_to_string()?;
+            (entry.parse)(&input).map_err(|err| err.in_file(source.label()))
+        });
+    debug!(day = entry.day, elapsed = ?start.elapsed(), "Prepared input");
+    (parsed, start.elapsed())
+}
+
+impl RunOutcome {
+    /// Runs every solution in order.
+    /// Parts of the same day share one parse of the input, so the time of each
+    /// part includes the shared reading and parsing.
+    ///
+    /// # Param
+    /// * `input_dir` - The configured directory of inputs, searched when `input` is not given
+    pub fn run_all(
+        entries: &[&'static SolutionEntry],
+        input: Option<InputSource>,
+        input_dir: Option<&Path>,
+    ) -> Vec<Self> {
+        let mut outcomes = vec![];
+        let mut prepared: Option<(&SolutionEntry, ParsedInput, Duration)> = None;
+        for &entry in entries {
+            let reuse = prepared
+                .as_ref()
+                .is_some_and(|(owner, ..)| owner.shares_input_with(entry));
+            if !reuse {
+                prepared = None;
+                // A failed parse is not kept, so the next part reports its own error
+                match prepare_input(entry, input.clone(), input_dir) {
+                    (Ok(parsed), elapsed) => prepared = Some((entry, parsed, elapsed)),
+                    (Err(err), elapsed) => {
+                        outcomes.push(Self {
+                            entry,
+                            result: Err(err),
+                            elapsed,
+                        });
+                        continue;
+                    }
+                }
+            }
+
+            if let Some((_, parsed, prepare_time)) = &prepared {
+                let start = Instant::now();
+                let result = (entry.solve)(parsed);
+                debug!(solution = %entry.name(), elapsed = ?start.elapsed(), "Solved");
+                outcomes.push(Self {
+                    entry,
+                    result,
+                    elapsed: *prepare_time + start.elapsed(),
+                });
+            }
+        }
+        outcomes
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// `err` on a single line, so multi-line messages like the paths tried for an input fit in a table row
+pub fn format_error_inline(err: &AdventErrors) -> String {
+    err.to_string()
+        .lines()
+        .map(str::trim)
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// Table of day, part, answer, status and elapsed time for each outcome
+pub struct SummaryTable<'a> {
+    outcomes: &'a [RunOutcome],
+}
+
+impl<'a> SummaryTable<'a> {
+    const HEADERS: [&'static str; 5] = ["Day", "Part", "Answer", "Status", "Time"];
+
+    pub fn new(outcomes: &'a [RunOutcome]) -> Self {
+        Self { outcomes }
+    }
+
+    pub fn num_failed(&self) -> usize {
+        self.outcomes
+            .iter()
+            .filter(|outcome| !outcome.is_ok())
+            .count()
+    }
+
+    fn rows(&self) -> Vec<[String; 5]> {
+        self.outcomes
+            .iter()
+            .map(|outcome| {
+                let (answer, status) = match &outcome.result {
+                    Ok(answer) => (format_answer_inline(answer), "ok"),
+                    Err(err) => (format_error_inline(err), "failed"),
+                };
+                [
+                    outcome.entry.day.to_string(),
+                    outcome.entry.part.to_string(),
+                    answer,
+                    status.to_string(),
+                    format!("{:.2?}", outcome.elapsed),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for SummaryTable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", format_table(&Self::HEADERS, &self.rows()))?;
+        write!(
+            f,
+            "{} passed, {} failed",
+            self.outcomes.len() - self.num_failed(),
+            self.num_failed()
+        )
+    }
+}
+
+/// Rendered diagnostics of the failures that point into an input.
+/// Both parts of a day fail on the same bad input, so it is only rendered once.
+pub(crate) fn diagnostics(outcomes: &[RunOutcome]) -> Vec<String> {
+    let mut rendered: Vec<String> = vec![];
+    let mut previous: Option<&SolutionEntry> = None;
+    for outcome in outcomes {
+        let Err(err) = &outcome.result else {
+            continue;
+        };
+        let Some(diagnostic) = render_diagnostic(err) else {
+            continue;
+        };
+        if previous.is_some_and(|previous| previous.shares_input_with(outcome.entry))
+            && rendered.last() == Some(&diagnostic)
+        {
+            continue;
+        }
+        previous = Some(outcome.entry);
+        rendered.push(diagnostic);
+    }
+    rendered
+}
+
+/// Renders rows as left-aligned columns under a header and separator line
+pub(crate) fn format_table<const N: usize>(headers: &[&str; N], rows: &[[String; N]]) -> String {
+    let mut widths = headers.map(str::len);
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |cells: [&str; N]| {
+        let line: Vec<String> = cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        line.join("  ").trim_end().to_string()
+    };
+
+    let separator = widths.map(|width| "-".repeat(width));
+    let mut lines = vec![
+        format_row(*headers),
+        format_row(separator.each_ref().map(String::as_str)),
+    ];
+    lines.extend(
+        rows.iter()
+            .map(|row| format_row(row.each_ref().map(String::as_str))),
+    );
+    lines.join("\n")
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use advent_common::{errors::AdventErrors, parse::Span, registry::Part};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Solution whose outcomes are made up by the tests rather than run
+    pub(crate) static DAY_ONE: SolutionEntry = SolutionEntry {
+        year: 2022,
+        day: 1,
+        part: Part::A,
+        input_file: "input.txt",
+        manifest_dir: "",
+        parse: |_| Ok(Box::new(())),
+        solve: |_| Ok(Answer::integer(0)),
+    };
+
+    #[test]
+    fn test_summary_table() {
+        let outcomes = [
+            RunOutcome {
+                entry: &DAY_ONE,
+                result: Ok(Answer::integer(42)),
+                elapsed: Duration::from_millis(3),
+            },
+            RunOutcome {
+                entry: &DAY_ONE,
+                result: Err(AdventErrors::AdventError("boom".to_string())),
+                elapsed: Duration::from_millis(1),
+            },
+        ];
+        let table = SummaryTable::new(&outcomes);
+        assert_eq!(table.num_failed(), 1);
+
+        let rendered = table.to_string();
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), 5, "{}", rendered);
+        assert!(lines[0].starts_with("Day  Part  Answer"), "{}", rendered);
+        assert!(lines[2].contains("42") && lines[2].contains("ok"));
+        assert!(lines[3].contains("failed"));
+        assert_eq!(lines[4], "1 passed, 1 failed");
+    }
+
+    #[test]
+    fn test_format_error_inline() {
+        let err = AdventErrors::InputNotFound {
+            day: 5,
+            tried: vec!["/a/input.txt".into(), "/b/input.txt".into()],
+        };
+        assert!(err.to_string().contains('\n'));
+        assert_eq!(
+            format_error_inline(&err),
+            "Could not find input for day 5. Tried: - /a/input.txt - /b/input.txt"
+        );
+    }
+
+    static NUM_PARSES: AtomicUsize = AtomicUsize::new(0);
+
+    fn counting_parse(input: &str) -> AdventResult<ParsedInput> {
+        NUM_PARSES.fetch_add(1, Ordering::SeqCst);
+        Ok(Box::new(Span::new(1, input.trim()).parse::<i128>()?))
+    }
+
+    fn solve_with(parsed: &ParsedInput, offset: i128) -> AdventResult<Answer> {
+        let value = parsed.downcast_ref::<i128>().expect("Parsed as i128");
+        Ok(Answer::integer(value + offset))
+    }
+
+    static COUNTED_A: SolutionEntry = SolutionEntry {
+        year: 2022,
+        day: 1,
+        part: Part::A,
+        input_file: "input.txt",
+        manifest_dir: "",
+        parse: counting_parse,
+        solve: |parsed| solve_with(parsed, 0),
+    };
+
+    static COUNTED_B: SolutionEntry = SolutionEntry {
+        part: Part::B,
+        solve: |parsed| solve_with(parsed, 1),
+        ..COUNTED_A
+    };
+
+    #[test]
+    fn test_run_all_parses_once_per_day() {
+        let dir = tempfile::tempdir().expect("Temp dir");
+        let input_path = dir.path().join("input.txt");
+        std::fs::write(&input_path, "41\n").expect("Input written");
+
+        let outcomes = RunOutcome::run_all(
+            &[&COUNTED_A, &COUNTED_B],
+            Some(InputSource::File(input_path)),
+            None,
+        );
+        assert_eq!(NUM_PARSES.load(Ordering::SeqCst), 1);
+        let answers: Vec<Answer> = outcomes
+            .into_iter()
+            .map(|outcome| outcome.result.expect("Should solve"))
+            .collect();
+        assert_eq!(answers, vec![Answer::integer(41), Answer::integer(42)]);
+    }
+}
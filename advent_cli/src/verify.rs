@@ -0,0 +1,191 @@
+//! Reruns solutions and compares them against the known-answer store
+
+use clap::Args;
+use std::{fmt, path::PathBuf};
+
+use advent_common::{
+    answers::AnswerStore,
+    errors::{AdventErrors, AdventResult},
+    registry::{DaySelection, Part},
+};
+
+use crate::{
+    cli::select_solutions,
+    config::Settings,
+    output::format_answer_inline,
+    summary::{diagnostics, format_error_inline, format_table, RunOutcome},
+};
+
+/// Arguments of the `verify` command
+#[derive(Args, Clone, Debug)]
+pub struct VerifyArgs {
+    /// Day to verify, or an inclusive range such as `3..5`. Every day is verified when omitted
+    days: Option<DaySelection>,
+    /// Part to verify (`a` or `b`). Both parts are verified when omitted
+    part: Option<Part>,
+    /// Record the answer of every solution that does not have one yet
+    #[arg(long)]
+    record: bool,
+    /// Path to the answers file. Searched for from the current directory upwards when not given
+    #[arg(long)]
+    answers: Option<PathBuf>,
+}
+
+/// How a solution's answer compares to the recorded one
+#[derive(Clone, Debug, PartialEq)]
+pub enum VerifyStatus {
+    Match,
+    Mismatch,
+    /// No answer is recorded for the solution
+    Unrecorded,
+    /// The answer was just added with `--record`
+    Recorded,
+    Failed,
+}
+
+impl VerifyStatus {
+    /// Whether the status should make the command fail
+    pub fn is_failure(&self) -> bool {
+        matches!(self, VerifyStatus::Mismatch | VerifyStatus::Failed)
+    }
+}
+
+impl fmt::Display for VerifyStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            VerifyStatus::Match => "ok",
+            VerifyStatus::Mismatch => "MISMATCH",
+            VerifyStatus::Unrecorded => "unrecorded",
+            VerifyStatus::Recorded => "recorded",
+            VerifyStatus::Failed => "failed",
+        };
+        write!(f, "{}", status)
+    }
+}
+
+/// Compares a run against the store, recording the answer when asked to
+pub fn verify_outcome(
+    outcome: &RunOutcome,
+    store: &mut AnswerStore,
+    record: bool,
+) -> (VerifyStatus, Option<String>) {
+    let entry = outcome.entry;
+    let actual = outcome
+        .result
+        .as_ref()
+        .map(|answer| answer.value.to_string());
+    let expected = store
+        .get(entry.year, entry.day, entry.part)
+        .map(str::to_owned);
+
+    let status = match (&actual, &expected) {
+        (Err(_), _) => VerifyStatus::Failed,
+        (Ok(actual), Some(expected)) if actual == expected => VerifyStatus::Match,
+        (Ok(_), Some(_)) => VerifyStatus::Mismatch,
+        (Ok(actual), None) if record => {
+            store.record(entry.year, entry.day, entry.part, actual);
+            VerifyStatus::Recorded
+        }
+        (Ok(_), None) => VerifyStatus::Unrecorded,
+    };
+    (status, expected)
+}
+
+/// Runs the `verify` command
+pub(crate) fn run_verify(args: &VerifyArgs, settings: &Settings) -> AdventResult<()> {
+    let answers_path = match &args.answers {
+        Some(path) => path.to_owned(),
+        None => AnswerStore::default_path()?,
+    };
+    let mut store = AnswerStore::load(&answers_path)?;
+
+    let mut rows = vec![];
+    let mut num_failed = 0;
+    let selected = select_solutions(settings.year, args.days, args.part, None)?;
+    let outcomes = RunOutcome::run_all(&selected, None, settings.input_dir.as_deref());
+    for outcome in outcomes.iter() {
+        let entry = outcome.entry;
+        let (status, expected) = verify_outcome(outcome, &mut store, args.record);
+        if status.is_failure() {
+            num_failed += 1;
+        }
+
+        let actual = match &outcome.result {
+            Ok(answer) => format_answer_inline(answer),
+            Err(err) => format_error_inline(err),
+        };
+        rows.push([
+            entry.day.to_string(),
+            entry.part.to_string(),
+            status.to_string(),
+            expected.unwrap_or_else(|| "-".to_string()),
+            actual,
+        ]);
+    }
+
+    println!(
+        "{}",
+        format_table(&["Day", "Part", "Status", "Expected", "Actual"], &rows)
+    );
+    for diagnostic in diagnostics(&outcomes) {
+        eprintln!("\n{}", diagnostic);
+    }
+
+    if args.record {
+        store.save(&answers_path)?;
+        println!("Answers saved to {}", answers_path.display());
+    }
+
+    match num_failed {
+        0 => Ok(()),
+        num_failed => Err(AdventErrors::AdventError(format!(
+            "{} of {} solutions did not match their recorded answer",
+            num_failed,
+            rows.len()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::summary::tests::DAY_ONE;
+    use advent_common::command::Answer;
+    use std::time::Duration;
+
+    fn outcome(result: AdventResult<Answer>) -> RunOutcome {
+        RunOutcome {
+            entry: &DAY_ONE,
+            result,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_verify_outcome() {
+        let mut store = AnswerStore::default();
+
+        let (status, _) = verify_outcome(&outcome(Ok(Answer::integer(42))), &mut store, false);
+        assert_eq!(status, VerifyStatus::Unrecorded);
+        assert_eq!(store.get(2022, 1, Part::A), None);
+
+        let (status, _) = verify_outcome(&outcome(Ok(Answer::integer(42))), &mut store, true);
+        assert_eq!(status, VerifyStatus::Recorded);
+        assert_eq!(store.get(2022, 1, Part::A), Some("42"));
+
+        let (status, _) = verify_outcome(&outcome(Ok(Answer::integer(42))), &mut store, false);
+        assert_eq!(status, VerifyStatus::Match);
+
+        // A recorded answer is never overwritten by --record
+        let (status, expected) =
+            verify_outcome(&outcome(Ok(Answer::integer(41))), &mut store, true);
+        assert_eq!(status, VerifyStatus::Mismatch);
+        assert_eq!(expected.as_deref(), Some("42"));
+        assert_eq!(store.get(2022, 1, Part::A), Some("42"));
+
+        let failed = outcome(Err(AdventErrors::AdventError("boom".to_string())));
+        let (status, _) = verify_outcome(&failed, &mut store, true);
+        assert!(status.is_failure());
+    }
+}
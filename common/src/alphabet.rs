@@ -0,0 +1,276 @@
+//! Sets of characters stored as a bitset, i.e. the items in a day 3 rucksack.
+//! Each character of an `Alphabet` owns one bit, and its priority is its 1-based position.
+
+use std::{
+    fmt,
+    ops::{BitAnd, BitOr, Sub},
+};
+
+use crate::errors::{AdventErrors, AdventResult};
+
+/// Ordered list of up to 128 distinct characters
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Alphabet {
+    chars: &'static str,
+}
+
+impl Alphabet {
+    pub const LOWERCASE: Alphabet = Alphabet::new("abcdefghijklmnopqrstuvwxyz");
+    pub const UPPERCASE: Alphabet = Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+    /// Lowercase then uppercase letters, so `a` has priority 1 and `Z` priority 52
+    pub const LETTERS: Alphabet =
+        Alphabet::new("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ");
+    pub const DIGITS: Alphabet = Alphabet::new("0123456789");
+
+    /// # Panics
+    /// When `chars` is longer than 128 bytes
+    pub const fn new(chars: &'static str) -> Self {
+        assert!(
+            chars.len() <= 128,
+            "An alphabet holds at most 128 characters"
+        );
+        Self { chars }
+    }
+
+    /// 0-based position of `item` in the alphabet
+    pub fn index_of(&self, item: char) -> Option<usize> {
+        self.chars.chars().position(|member| member == item)
+    }
+
+    pub fn char_at(&self, idx: usize) -> Option<char> {
+        self.chars.chars().nth(idx)
+    }
+
+    pub fn len(&self) -> usize {
+        self.chars.chars().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    /// 1-based position of `item` in the alphabet
+    pub fn priority(&self, item: char) -> Option<usize> {
+        self.index_of(item).map(|idx| idx + 1)
+    }
+}
+
+/// Set of characters from one alphabet
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AlphabetSet {
+    alphabet: Alphabet,
+    bits: u128,
+}
+
+impl AlphabetSet {
+    /// Empty set over `alphabet`
+    pub fn new(alphabet: Alphabet) -> Self {
+        Self { alphabet, bits: 0 }
+    }
+
+    /// Set of every character of `text`
+    ///
+    /// # Return
+    /// An error when a character is not part of `alphabet`
+    pub fn from_text(alphabet: Alphabet, text: &str) -> AdventResult<Self> {
+        let mut set = Self::new(alphabet);
+        for item in text.chars() {
+            set.insert(item)?;
+        }
+        Ok(set)
+    }
+
+    pub fn alphabet(&self) -> Alphabet {
+        self.alphabet
+    }
+
+    /// # Return
+    /// Whether `item` was newly added, or an error when it is not part of the alphabet
+    pub fn insert(&mut self, item: char) -> AdventResult<bool> {
+        let idx = self.alphabet.index_of(item).ok_or_else(|| {
+            AdventErrors::AdventError(format!("'{}' is not part of the alphabet", item))
+        })?;
+        let bit = 1u128 << idx;
+        let is_new = self.bits & bit == 0;
+        self.bits |= bit;
+        Ok(is_new)
+    }
+
+    pub fn contains(&self, item: char) -> bool {
+        self.alphabet
+            .index_of(item)
+            .is_some_and(|idx| self.bits & (1u128 << idx) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// 0-based alphabet positions of the members, in alphabet order
+    fn indices(&self) -> impl Iterator<Item = usize> {
+        let mut bits = self.bits;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let idx = bits.trailing_zeros() as usize;
+            bits &= bits - 1;
+            Some(idx)
+        })
+    }
+
+    /// Members in alphabet order
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.indices().filter_map(|idx| self.alphabet.char_at(idx))
+    }
+
+    /// 1-based alphabet positions of the members, in alphabet order
+    pub fn priorities(&self) -> impl Iterator<Item = usize> {
+        self.indices().map(|idx| idx + 1)
+    }
+
+    /// The only member of the set
+    ///
+    /// # Return
+    /// `None` when the set does not hold exactly one character
+    pub fn single(&self) -> Option<char> {
+        match self.len() {
+            1 => self.chars().next(),
+            _ => None,
+        }
+    }
+
+    /// # Panics
+    /// When the sets use different alphabets, as their bits then stand for different characters
+    fn combine(&self, other: &AlphabetSet, op: impl Fn(u128, u128) -> u128) -> AlphabetSet {
+        assert_eq!(
+            self.alphabet, other.alphabet,
+            "Sets over different alphabets"
+        );
+        Self {
+            alphabet: self.alphabet,
+            bits: op(self.bits, other.bits),
+        }
+    }
+
+    pub fn union(&self, other: &AlphabetSet) -> AlphabetSet {
+        self.combine(other, |a, b| a | b)
+    }
+
+    pub fn intersection(&self, other: &AlphabetSet) -> AlphabetSet {
+        self.combine(other, |a, b| a & b)
+    }
+
+    pub fn difference(&self, other: &AlphabetSet) -> AlphabetSet {
+        self.combine(other, |a, b| a & !b)
+    }
+
+    /// Characters found in every set
+    ///
+    /// # Return
+    /// `None` when there are no sets
+    pub fn intersect_all(sets: impl IntoIterator<Item = AlphabetSet>) -> Option<AlphabetSet> {
+        sets.into_iter().reduce(|all, set| all.intersection(&set))
+    }
+
+    /// Characters found in any set
+    ///
+    /// # Return
+    /// `None` when there are no sets
+    pub fn union_all(sets: impl IntoIterator<Item = AlphabetSet>) -> Option<AlphabetSet> {
+        sets.into_iter().reduce(|all, set| all.union(&set))
+    }
+}
+
+impl BitAnd for AlphabetSet {
+    type Output = AlphabetSet;
+    fn bitand(self, other: AlphabetSet) -> AlphabetSet {
+        self.intersection(&other)
+    }
+}
+
+impl BitOr for AlphabetSet {
+    type Output = AlphabetSet;
+    fn bitor(self, other: AlphabetSet) -> AlphabetSet {
+        self.union(&other)
+    }
+}
+
+impl Sub for AlphabetSet {
+    type Output = AlphabetSet;
+    fn sub(self, other: AlphabetSet) -> AlphabetSet {
+        self.difference(&other)
+    }
+}
+
+impl fmt::Display for AlphabetSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for item in self.chars() {
+            write!(f, "{}", item)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    fn letters(text: &str) -> AlphabetSet {
+        AlphabetSet::from_text(Alphabet::LETTERS, text).expect("Only letters")
+    }
+
+    #[test]
+    fn test_priorities() {
+        assert_eq!(Alphabet::LETTERS.priority('a'), Some(1));
+        assert_eq!(Alphabet::LETTERS.priority('Z'), Some(52));
+        assert_eq!(Alphabet::LETTERS.priority('?'), None);
+
+        let set = letters("Lpap");
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.to_string(), "apL");
+        assert_eq!(set.priorities().collect::<Vec<_>>(), vec![1, 16, 38]);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let first = letters("vJrwpWtwJgWr");
+        let second = letters("hcsFMMfFFhFp");
+        assert_eq!((first & second).single(), Some('p'));
+        assert_eq!((first | second).len(), first.len() + second.len() - 1);
+        assert!(!(first - second).contains('p'));
+        assert!((first - second).contains('v'));
+
+        let group = [
+            letters("vJrwpWtwJgWrhcsFMMfFFhFp"),
+            letters("jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL"),
+            letters("PmmdzqPrVvPwwTWBwg"),
+        ];
+        let common = AlphabetSet::intersect_all(group).expect("Not empty");
+        assert_eq!(common.single(), Some('r'));
+        assert_eq!(AlphabetSet::intersect_all([]), None);
+        assert!(AlphabetSet::union_all(group).is_some_and(|all| all.contains('B')));
+    }
+
+    #[test]
+    #[should_panic(expected = "Sets over different alphabets")]
+    fn test_mixed_alphabets() {
+        let digits = AlphabetSet::from_text(Alphabet::DIGITS, "0").expect("Digits");
+        let _ = letters("abc") & digits;
+    }
+
+    #[test]
+    fn test_outside_alphabet() {
+        let mut set = AlphabetSet::new(Alphabet::DIGITS);
+        assert!(set.insert('7').expect("A digit"));
+        assert!(!set.insert('7').expect("A digit"));
+        assert!(set.insert('x').is_err());
+        assert!(!set.contains('x'));
+        assert!(AlphabetSet::from_text(Alphabet::LOWERCASE, "abC").is_err());
+    }
+}
@@ -0,0 +1,189 @@
+//! Known-answer store (answers.toml) used to catch regressions in solved puzzles
+//!
+//! ```toml
+//! [2022.day1]
+//! a = "68923"
+//! b = "200044"
+//!
+//! # Answers the website accepted through `submit`
+//! [accepted.2022.day1]
+//! a = "68923"
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    errors::{AdventErrors, AdventResult},
+    registry::Part,
+};
+
+/// Name of the file holding the accepted answers
+pub const ANSWERS_FILE_NAME: &str = "answers.toml";
+
+type DayAnswers = BTreeMap<String, String>;
+type YearAnswers = BTreeMap<String, DayAnswers>;
+
+/// Known answers keyed by year, day and part.
+/// Answers confirmed by the website are also kept apart in `accepted`,
+/// since `verify --record` records whatever the solutions return.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnswerStore {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    accepted: BTreeMap<String, YearAnswers>,
+    #[serde(flatten)]
+    years: BTreeMap<String, YearAnswers>,
+}
+
+impl AnswerStore {
+    /// Closest answers file walking up from the current directory,
+    /// or one in the current directory if none exists yet
+    pub fn default_path() -> AdventResult<PathBuf> {
+        let cwd = env::current_dir()?;
+        let existing = cwd
+            .ancestors()
+            .map(|dir| dir.join(ANSWERS_FILE_NAME))
+            .find(|candidate| candidate.is_file());
+        Ok(existing.unwrap_or_else(|| cwd.join(ANSWERS_FILE_NAME)))
+    }
+
+    /// Loads the store, starting empty if the file does not exist
+    pub fn load(path: &Path) -> AdventResult<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        toml::from_str(&fs::read_to_string(path)?).map_err(|err| {
+            AdventErrors::AnswerStoreError(format!("{}: {}", path.display(), err.message()))
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> AdventResult<()> {
+        fs::write(path, self.to_toml()?)?;
+        Ok(())
+    }
+
+    pub fn from_toml(contents: &str) -> AdventResult<Self> {
+        toml::from_str(contents)
+            .map_err(|err| AdventErrors::AnswerStoreError(err.message().to_string()))
+    }
+
+    pub fn to_toml(&self) -> AdventResult<String> {
+        toml::to_string(self).map_err(|err| AdventErrors::AnswerStoreError(err.to_string()))
+    }
+
+    /// The known answer, if one was recorded
+    pub fn get(&self, year: u16, day: u8, part: Part) -> Option<&str> {
+        Self::lookup(&self.years, year, day, part)
+    }
+
+    /// The answer the website accepted, if one was submitted
+    pub fn accepted(&self, year: u16, day: u8, part: Part) -> Option<&str> {
+        Self::lookup(&self.accepted, year, day, part)
+    }
+
+    /// Records `answer`, replacing any previous one
+    pub fn record(&mut self, year: u16, day: u8, part: Part, answer: &str) {
+        Self::insert(&mut self.years, year, day, part, answer);
+    }
+
+    /// Records `answer` as confirmed by the website
+    pub fn record_accepted(&mut self, year: u16, day: u8, part: Part, answer: &str) {
+        self.record(year, day, part, answer);
+        Self::insert(&mut self.accepted, year, day, part, answer);
+    }
+
+    fn lookup(
+        table: &BTreeMap<String, YearAnswers>,
+        year: u16,
+        day: u8,
+        part: Part,
+    ) -> Option<&str> {
+        table
+            .get(&year.to_string())?
+            .get(&Self::day_key(day))?
+            .get(&part.to_string())
+            .map(String::as_str)
+    }
+
+    fn insert(
+        table: &mut BTreeMap<String, YearAnswers>,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+    ) {
+        table
+            .entry(year.to_string())
+            .or_default()
+            .entry(Self::day_key(day))
+            .or_default()
+            .insert(part.to_string(), answer.to_owned());
+    }
+
+    fn day_key(day: u8) -> String {
+        format!("day{}", day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_record_and_get() {
+        let mut store = AnswerStore::default();
+        assert_eq!(store.get(2022, 3, Part::A), None);
+
+        store.record(2022, 3, Part::A, "157");
+        store.record(2022, 3, Part::B, "70");
+        store.record(2022, 3, Part::A, "158");
+        assert_eq!(store.get(2022, 3, Part::A), Some("158"));
+        assert_eq!(store.get(2022, 3, Part::B), Some("70"));
+        assert_eq!(store.get(2021, 3, Part::A), None);
+    }
+
+    #[test]
+    fn test_toml_round_trip() {
+        let contents = "[2022.day1]\na = \"24000\"\nb = \"45000\"\n";
+        let store = AnswerStore::from_toml(contents).expect("Answers should parse");
+        assert_eq!(store.get(2022, 1, Part::A), Some("24000"));
+
+        let written = store.to_toml().expect("Answers should serialize");
+        let reread = AnswerStore::from_toml(&written).expect("Written answers should parse");
+        assert_eq!(reread, store);
+    }
+
+    #[test]
+    fn test_accepted_kept_apart() {
+        let mut store = AnswerStore::default();
+        store.record(2022, 1, Part::A, "68923");
+        assert_eq!(store.accepted(2022, 1, Part::A), None);
+
+        store.record_accepted(2022, 1, Part::B, "200044");
+        assert_eq!(store.get(2022, 1, Part::B), Some("200044"));
+        assert_eq!(store.accepted(2022, 1, Part::B), Some("200044"));
+
+        let written = store.to_toml().expect("Answers should serialize");
+        assert!(written.contains("[accepted.2022.day1]"), "{}", written);
+        let reread = AnswerStore::from_toml(&written).expect("Written answers should parse");
+        assert_eq!(reread, store);
+    }
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        let dir = tempfile::tempdir().expect("Should create temp dir");
+        let path = dir.path().join(ANSWERS_FILE_NAME);
+        let mut store = AnswerStore::load(&path).expect("Missing file is not an error");
+        assert_eq!(store, AnswerStore::default());
+
+        store.record(2022, 4, Part::B, "4");
+        store.save(&path).expect("Should save");
+        let reloaded = AnswerStore::load(&path).expect("Saved file should load");
+        assert_eq!(reloaded.get(2022, 4, Part::B), Some("4"));
+    }
+}
@@ -1,13 +1,99 @@
 //! File used to define the command trait for all days
 
+use std::fmt;
+
 use crate::errors::{AdventErrors, AdventResult};
 
-/// Make sure all command implement the AdventSolution trait
+/// The value a puzzle asks for
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnswerValue {
+    Integer(i128),
+    Text(String),
+    /// Multi-line output meant to be read as a picture, i.e. letters drawn on a grid
+    Art(String),
+}
+
+impl fmt::Display for AnswerValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswerValue::Integer(value) => write!(f, "{}", value),
+            AnswerValue::Text(value) | AnswerValue::Art(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// Result of a solution: the value plus an optional description of what it means
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub value: AnswerValue,
+    pub description: Option<String>,
+}
+
+impl Answer {
+    pub fn new(value: AnswerValue) -> Self {
+        Self {
+            value,
+            description: None,
+        }
+    }
+
+    pub fn integer(value: impl Into<i128>) -> Self {
+        Self::new(AnswerValue::Integer(value.into()))
+    }
+
+    pub fn text(value: impl Into<String>) -> Self {
+        Self::new(AnswerValue::Text(value.into()))
+    }
+
+    pub fn art(value: impl Into<String>) -> Self {
+        Self::new(AnswerValue::Art(value.into()))
+    }
+
+    /// Adds a human readable description, i.e. "Total Priority"
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+/// Make sure all command implement the AdventSolution trait.
+/// The input is parsed once and shared by both parts.
 pub trait AdventSolution {
-    fn find_solution(&self) -> AdventResult<String> {
+    /// Parsed form of the puzzle input
+    type Input: 'static;
+
+    /// Converts the raw input text into `Self::Input`
+    fn parse(input: &str) -> AdventResult<Self::Input>;
+
+    /// Solves the first half of the puzzle
+    fn part1(input: &Self::Input) -> AdventResult<Answer>;
+
+    /// Solves the second half of the puzzle
+    fn part2(_input: &Self::Input) -> AdventResult<Answer> {
         // panic!("AdventSolution not implemented for the solution struct!");
         Err(AdventErrors::SolutionNotImplemented(
-            "AdventSolution not implemented for the solution struct!".to_string(),
+            "Part 2 not implemented for the solution struct!".to_string(),
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_answer_value_display() {
+        assert_eq!(Answer::integer(157u64).value.to_string(), "157");
+        assert_eq!(Answer::integer(-3).value.to_string(), "-3");
+        assert_eq!(Answer::text("CMZ").value.to_string(), "CMZ");
+        assert_eq!(Answer::art("#.\n.#").value.to_string(), "#.\n.#");
+    }
+
+    #[test]
+    fn test_with_description() {
+        let answer = Answer::integer(70u8).with_description("Total Priority");
+        assert_eq!(answer.value, AnswerValue::Integer(70));
+        assert_eq!(answer.description.as_deref(), Some("Total Priority"));
+    }
+}
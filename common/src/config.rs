@@ -0,0 +1,198 @@
+//! Loads the optional configuration files.
+//!
+//! Two files are read, with the project file overriding the user file:
+//! 1. The user file, `$XDG_CONFIG_HOME/advent/config.toml` or `~/.config/advent/config.toml`
+//! 2. The project file, the closest `.advent.toml` walking up from the current directory
+//!
+//! ```toml
+//! input_dir = "inputs"
+//! year = 2022
+//! format = "text" # or "json", "csv"
+//! verbosity = "normal" # or "quiet", "verbose", "debug", "trace"
+//!
+//! [bench]
+//! iterations = 100
+//! warmup = 5
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::{
+    env, fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::errors::{AdventErrors, AdventResult};
+
+/// Name of the project-level configuration file
+pub const CONFIG_FILE_NAME: &str = ".advent.toml";
+
+/// Path of the user-level configuration file, relative to the user's config directory
+pub const USER_CONFIG_PATH: &str = "advent/config.toml";
+
+/// How answers and summaries are printed
+#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Sentences and tables for people
+    #[default]
+    Text,
+    /// A JSON array with one object per solution
+    Json,
+    /// A header line then one line per solution
+    Csv,
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputFormat::Text => write!(f, "text"),
+            OutputFormat::Json => write!(f, "json"),
+            OutputFormat::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+/// How much the CLI reports besides the answers
+#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Verbosity {
+    Quiet,
+    #[default]
+    Normal,
+    Verbose,
+    Debug,
+    Trace,
+}
+
+/// Settings of the `bench` command
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct BenchConfig {
+    /// Number of timed runs
+    pub iterations: Option<u32>,
+    /// Number of untimed runs before measuring
+    pub warmup: Option<u32>,
+}
+
+/// Settings that can be provided via the configuration files.
+/// Every setting is optional so the files can be layered.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct AdventConfig {
+    /// Directory containing a `dayN/` folder per day's input
+    pub input_dir: Option<PathBuf>,
+    /// Year whose solutions are run
+    pub year: Option<u16>,
+    pub format: Option<OutputFormat>,
+    pub verbosity: Option<Verbosity>,
+    pub bench: BenchConfig,
+}
+
+impl AdventConfig {
+    /// Loads the user configuration file overridden by the project configuration file.
+    ///
+    /// # Return
+    /// The default (empty) configuration if neither file exists
+    pub fn load() -> AdventResult<Self> {
+        Ok(Self::load_files()?
+            .into_iter()
+            .fold(Self::default(), |config, (_, file)| config.merge(file)))
+    }
+
+    /// Every configuration file found, from the lowest to the highest priority
+    pub fn load_files() -> AdventResult<Vec<(PathBuf, AdventConfig)>> {
+        let cwd = env::current_dir()?;
+        let paths = [Self::user_config_file(), Self::find_config_file(&cwd)];
+        paths
+            .into_iter()
+            .flatten()
+            .map(|path| Ok((path.clone(), Self::from_file(&path)?)))
+            .collect()
+    }
+
+    /// Parses the file at `path`. Relative paths inside it are resolved against its directory.
+    pub fn from_file(path: &Path) -> AdventResult<Self> {
+        let contents = fs::read_to_string(path)?;
+        let mut config: Self = toml::from_str(&contents).map_err(|err| {
+            AdventErrors::ConfigError(format!("{}: {}", path.display(), err.message()))
+        })?;
+
+        if let (Some(input_dir), Some(config_dir)) = (&config.input_dir, path.parent()) {
+            config.input_dir = Some(config_dir.join(input_dir));
+        }
+        Ok(config)
+    }
+
+    /// Combines two configurations, with the settings of `other` taking priority
+    pub fn merge(self, other: AdventConfig) -> AdventConfig {
+        AdventConfig {
+            input_dir: other.input_dir.or(self.input_dir),
+            year: other.year.or(self.year),
+            format: other.format.or(self.format),
+            verbosity: other.verbosity.or(self.verbosity),
+            bench: BenchConfig {
+                iterations: other.bench.iterations.or(self.bench.iterations),
+                warmup: other.bench.warmup.or(self.bench.warmup),
+            },
+        }
+    }
+
+    fn find_config_file(start: &Path) -> Option<PathBuf> {
+        start
+            .ancestors()
+            .map(|dir| dir.join(CONFIG_FILE_NAME))
+            .find(|candidate| candidate.is_file())
+    }
+
+    fn user_config_file() -> Option<PathBuf> {
+        Some(user_config_home()?.join(USER_CONFIG_PATH)).filter(|path| path.is_file())
+    }
+}
+
+/// The user's config directory, `$XDG_CONFIG_HOME` or `~/.config`
+pub fn user_config_home() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_project_overrides_user() {
+        let user: AdventConfig = toml::from_str(
+            "year = 2021\nverbosity = \"verbose\"\n[bench]\niterations = 10\nwarmup = 1",
+        )
+        .expect("Valid config");
+        let project: AdventConfig =
+            toml::from_str("year = 2022\n[bench]\nwarmup = 3").expect("Valid config");
+
+        let merged = user.merge(project);
+        assert_eq!(merged.year, Some(2022));
+        assert_eq!(merged.verbosity, Some(Verbosity::Verbose));
+        assert_eq!(merged.bench.iterations, Some(10));
+        assert_eq!(merged.bench.warmup, Some(3));
+        assert_eq!(merged.format, None);
+    }
+
+    #[test]
+    fn test_from_file() {
+        let dir = tempfile::tempdir().expect("Should create temp dir");
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        fs::write(&path, "input_dir = \"inputs\"\nformat = \"text\"").expect("Should write");
+
+        let config = AdventConfig::from_file(&path).expect("Valid config");
+        assert_eq!(config.input_dir, Some(dir.path().join("inputs")));
+        assert_eq!(config.format, Some(OutputFormat::Text));
+
+        fs::write(&path, "colour = true").expect("Should write");
+        match AdventConfig::from_file(&path) {
+            Err(AdventErrors::ConfigError(msg)) => assert!(msg.contains("colour"), "{}", msg),
+            other => panic!("Expected ConfigError, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Compiler-style rendering of errors that point into the puzzle input, i.e.
+//!
+//! ```text
+//! error: invalid digit found in string
+//!  --> day4/input.txt:2:7
+//!   |
+//! 2 | 2-4,6-x
+//!   |       ^ found `x`
+//!   |
+//!   = hint: Each line holds two ranges, i.e. `2-4,6-8`
+//! ```
+
+use std::error::Error;
+
+use crate::errors::{AdventErrors, InputSpan};
+
+/// Renders `err` as a diagnostic
+///
+/// # Return
+/// `None` when the error does not carry a location in the input
+pub fn render_diagnostic(err: &AdventErrors) -> Option<String> {
+    match err {
+        AdventErrors::InvalidInput {
+            location,
+            message,
+            hint,
+        } => Some(render(location, message, hint.as_deref())),
+        _ => None,
+    }
+}
+
+/// Renders `err` followed by every error that caused it, one per line, i.e.
+///
+/// ```text
+/// 2 of 2 solutions failed
+///   caused by: I/O error
+///   caused by: No such file or directory (os error 2)
+/// ```
+pub fn render_error_chain(err: &dyn Error) -> String {
+    let mut lines = vec![err.to_string()];
+    let mut source = err.source();
+    while let Some(cause) = source {
+        lines.push(format!("  caused by: {}", cause));
+        source = cause.source();
+    }
+    lines.join("\n")
+}
+
+/// Renders the source line of `location` with `text` underlined
+fn render(location: &InputSpan, message: &str, hint: Option<&str>) -> String {
+    let line_number = location.line.to_string();
+    let gutter = " ".repeat(line_number.len());
+    let underline = format!(
+        "{}{}",
+        " ".repeat(location.column.saturating_sub(1)),
+        "^".repeat(location.text.chars().count().max(1))
+    );
+    let label = if location.text.is_empty() {
+        "found nothing".to_string()
+    } else {
+        format!("found `{}`", location.text)
+    };
+
+    let mut lines = vec![
+        format!("error: {}", message),
+        format!("{}--> {}", gutter, location),
+        format!("{} |", gutter),
+        format!("{} | {}", line_number, location.source),
+        format!("{} | {} {}", gutter, underline, label),
+    ];
+    if let Some(hint) = hint {
+        lines.push(format!("{} |", gutter));
+        lines.push(format!("{} = hint: {}", gutter, hint));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_render_diagnostic() {
+        let line = "2-4,6-x";
+        let err = AdventErrors::invalid_input(
+            InputSpan::new(12, line, &line[6..]),
+            "invalid digit found in string",
+        )
+        .with_hint("Each line holds two ranges, i.e. `2-4,6-8`")
+        .in_file(Path::new("day4/input.txt"));
+
+        let expected = [
+            "error: invalid digit found in string",
+            "  --> day4/input.txt:12:7",
+            "   |",
+            "12 | 2-4,6-x",
+            "   |       ^ found `x`",
+            "   |",
+            "   = hint: Each line holds two ranges, i.e. `2-4,6-8`",
+        ]
+        .join("\n");
+        assert_eq!(render_diagnostic(&err).as_deref(), Some(expected.as_str()));
+    }
+
+    #[test]
+    fn test_render_without_span() {
+        let err = AdventErrors::AdventError("boom".to_string());
+        assert_eq!(render_diagnostic(&err), None);
+    }
+
+    #[test]
+    fn test_render_error_chain() {
+        let err = AdventErrors::SolutionsFailed {
+            failed: 1,
+            total: 2,
+            first: Box::new(AdventErrors::from(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "gone",
+            ))),
+        };
+        assert_eq!(
+            render_error_chain(&err),
+            "1 of 2 solutions failed\n  caused by: I/O error\n  caused by: gone"
+        );
+    }
+}
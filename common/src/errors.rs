@@ -1,18 +1,228 @@
-use std::num::ParseIntError;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
 use thiserror::Error;
 
 pub type AdventResult<T> = std::result::Result<T, AdventErrors>;
 
+/// Exit codes of the CLI, so scripts can tell failures apart
+pub mod exit_codes {
+    /// Configuration, I/O and any other failure
+    pub const GENERAL: u8 = 1;
+    /// Arguments that cannot be used together, the same code clap exits with on invalid arguments
+    pub const USAGE: u8 = 2;
+    pub const INPUT_NOT_FOUND: u8 = 3;
+    /// The input could not be parsed
+    pub const INVALID_INPUT: u8 = 4;
+    /// A solution failed or `verify` found a wrong answer
+    pub const SOLVER: u8 = 5;
+    pub const NOT_IMPLEMENTED: u8 = 6;
+    /// The website rejected a submitted answer
+    pub const WRONG_ANSWER: u8 = 7;
+}
+
 #[derive(Error, Debug)]
 pub enum AdventErrors {
-    #[error("Error solving advent challenge")]
+    #[error("{0}")]
     AdventError(String),
-    #[error("File does not exist error")]
-    FileError(String),
+    #[error("Could not find input for day {day}. Tried:{}", format_paths(.tried))]
+    InputNotFound { day: u8, tried: Vec<PathBuf> },
+    #[error("Invalid configuration file: {0}")]
+    ConfigError(String),
+    #[error("Invalid answers file: {0}")]
+    AnswerStoreError(String),
+    #[error("Could not create the day: {0}")]
+    ScaffoldError(String),
+    #[error("Request to Advent of Code failed: {0}")]
+    RequestError(String),
+    #[error("{0}")]
+    UsageError(String),
+    #[error("Answer rejected: {0}")]
+    WrongAnswer(String),
+    #[error("Already solved: {0}")]
+    AlreadySolved(String),
     #[error("I/O error")]
     IOError(#[from] std::io::Error),
-    #[error("String -> int error")]
-    ParseError(#[from] ParseIntError),
-    #[error("Solution not implemented")]
+    #[error("{location}: {message} (found `{}`)", location.text)]
+    InvalidInput {
+        location: Box<InputSpan>,
+        message: String,
+        /// Suggestion shown when the error is rendered as a diagnostic
+        hint: Option<String>,
+    },
+    #[error("{0}")]
     SolutionNotImplemented(String),
+    /// Several solutions ran and some failed. The first failure is kept as the source
+    #[error("{failed} of {total} solutions failed")]
+    SolutionsFailed {
+        failed: usize,
+        total: usize,
+        #[source]
+        first: Box<AdventErrors>,
+    },
+}
+
+impl AdventErrors {
+    /// Error for a piece of the input that could not be parsed
+    pub fn invalid_input(location: InputSpan, message: impl Into<String>) -> Self {
+        AdventErrors::InvalidInput {
+            location: Box::new(location),
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    /// Adds a hint to errors that point into the input
+    pub fn with_hint(mut self, new_hint: impl Into<String>) -> Self {
+        if let AdventErrors::InvalidInput { hint, .. } = &mut self {
+            *hint = Some(new_hint.into());
+        }
+        self
+    }
+
+    /// Exit code reporting this error, see `exit_codes`
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            AdventErrors::InputNotFound { .. } => exit_codes::INPUT_NOT_FOUND,
+            AdventErrors::InvalidInput { .. } => exit_codes::INVALID_INPUT,
+            AdventErrors::AdventError(_) => exit_codes::SOLVER,
+            AdventErrors::SolutionNotImplemented(_) => exit_codes::NOT_IMPLEMENTED,
+            AdventErrors::WrongAnswer(_) => exit_codes::WRONG_ANSWER,
+            AdventErrors::UsageError(_) => exit_codes::USAGE,
+            AdventErrors::SolutionsFailed { first, .. } => first.exit_code(),
+            AdventErrors::ConfigError(_)
+            | AdventErrors::AnswerStoreError(_)
+            | AdventErrors::ScaffoldError(_)
+            | AdventErrors::RequestError(_)
+            | AdventErrors::AlreadySolved(_)
+            | AdventErrors::IOError(_) => exit_codes::GENERAL,
+        }
+    }
+
+    /// Attaches the file the input was read from, for errors that point into the input
+    pub fn in_file(mut self, path: &Path) -> Self {
+        if let AdventErrors::InvalidInput { location, .. } = &mut self {
+            location.path.get_or_insert_with(|| path.to_owned());
+        }
+        self
+    }
+}
+
+/// Where in the input a piece of text sits
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputSpan {
+    /// File the input was read from, when known
+    pub path: Option<PathBuf>,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column of the first character of `text`
+    pub column: usize,
+    pub text: String,
+    /// The full line `text` was found on
+    pub source: String,
+}
+
+impl InputSpan {
+    /// Span of `text` on the line `line_number`
+    ///
+    /// # Param
+    /// * `line` - The full line of input
+    /// * `text` - A slice of `line`. Anything else is reported from the first column
+    pub fn new(line_number: usize, line: &str, text: &str) -> Self {
+        let offset = (text.as_ptr() as usize)
+            .checked_sub(line.as_ptr() as usize)
+            .filter(|offset| offset + text.len() <= line.len())
+            .unwrap_or(0);
+        Self {
+            path: None,
+            line: line_number,
+            column: line[..offset].chars().count() + 1,
+            text: text.to_string(),
+            source: line.to_string(),
+        }
+    }
+
+    /// Span covering the whole of `line`
+    pub fn line(line_number: usize, line: &str) -> Self {
+        Self::new(line_number, line, line)
+    }
+}
+
+impl fmt::Display for InputSpan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "{}:{}:{}", path.display(), self.line, self.column),
+            None => write!(f, "line {}, column {}", self.line, self.column),
+        }
+    }
+}
+
+fn format_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| format!("\n  - {}", path.display()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_input_span_columns() {
+        let line = "2-4,6-8";
+        let span = InputSpan::new(3, line, &line[4..7]);
+        assert_eq!((span.line, span.column, span.text.as_str()), (3, 5, "6-8"));
+
+        // Text that is not a slice of the line falls back to the first column
+        let elsewhere = String::from("6-8");
+        let span = InputSpan::new(3, line, &elsewhere);
+        assert_eq!(span.column, 1);
+    }
+
+    #[test]
+    fn test_invalid_input_message() {
+        let line = "1000x";
+        let err = AdventErrors::invalid_input(InputSpan::line(2, line), "invalid digit");
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: invalid digit (found `1000x`)"
+        );
+
+        let err = err.in_file(Path::new("day1/input.txt"));
+        assert_eq!(
+            err.to_string(),
+            "day1/input.txt:2:1: invalid digit (found `1000x`)"
+        );
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        let missing = AdventErrors::InputNotFound {
+            day: 1,
+            tried: vec![],
+        };
+        assert_eq!(missing.exit_code(), exit_codes::INPUT_NOT_FOUND);
+
+        let bad_input = AdventErrors::invalid_input(InputSpan::line(1, "x"), "Not a number");
+        let wrapped = AdventErrors::SolutionsFailed {
+            failed: 2,
+            total: 4,
+            first: Box::new(bad_input),
+        };
+        assert_eq!(wrapped.exit_code(), exit_codes::INVALID_INPUT);
+        assert_eq!(wrapped.to_string(), "2 of 4 solutions failed");
+
+        let not_done = AdventErrors::SolutionNotImplemented("Part 2".to_string());
+        assert_eq!(not_done.exit_code(), exit_codes::NOT_IMPLEMENTED);
+        assert_eq!(not_done.to_string(), "Part 2");
+
+        let wrong = AdventErrors::WrongAnswer("`579` is too low".to_string());
+        assert_eq!(wrong.exit_code(), exit_codes::WRONG_ANSWER);
+
+        let usage = AdventErrors::UsageError("--input with several days".to_string());
+        assert_eq!(usage.exit_code(), exit_codes::USAGE);
+    }
 }
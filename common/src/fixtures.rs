@@ -0,0 +1,131 @@
+//! Test harness running each day's worked examples through its registered solutions.
+//!
+//! Examples live next to the day's crate as `examples/<name>.txt`, with the expected
+//! answers in `examples/<name>.toml`:
+//!
+//! ```toml
+//! a = "157"
+//! b = "70"
+//! ```
+//!
+//! A part without an expected answer is skipped for that example.
+
+use serde::Deserialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    errors::{AdventErrors, AdventResult},
+    registry::{self, Part},
+};
+
+/// Directory, relative to a day's crate, holding its examples
+pub const EXAMPLES_DIR: &str = "examples";
+
+/// Expected answers of one example
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ExpectedAnswers {
+    pub a: Option<String>,
+    pub b: Option<String>,
+}
+
+impl ExpectedAnswers {
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::A => self.a.as_deref(),
+            Part::B => self.b.as_deref(),
+        }
+    }
+}
+
+/// A worked example and the answers it should produce
+#[derive(Clone, Debug)]
+pub struct Example {
+    pub input_path: PathBuf,
+    pub input: String,
+    pub expected: ExpectedAnswers,
+}
+
+/// Loads every example in `<manifest_dir>/examples`, sorted by file name
+pub fn load_examples(manifest_dir: &str) -> AdventResult<Vec<Example>> {
+    let examples_dir = Path::new(manifest_dir).join(EXAMPLES_DIR);
+    let mut input_paths: Vec<PathBuf> = fs::read_dir(&examples_dir)?
+        .map(|dir_entry| dir_entry.map(|dir_entry| dir_entry.path()))
+        .collect::<Result<_, _>>()?;
+    input_paths.retain(|path| path.extension().is_some_and(|ext| ext == "txt"));
+    input_paths.sort();
+
+    input_paths
+        .into_iter()
+        .map(|input_path| {
+            let answers_path = input_path.with_extension("toml");
+            let expected = toml::from_str(&fs::read_to_string(&answers_path)?).map_err(|err| {
+                AdventErrors::AdventError(format!("{}: {}", answers_path.display(), err.message()))
+            })?;
+            Ok(Example {
+                input: fs::read_to_string(&input_path)?,
+                input_path,
+                expected,
+            })
+        })
+        .collect()
+}
+
+/// Runs every example of `day` through its registered solutions.
+///
+/// # Panics
+/// If there are no examples, nothing is registered for the day,
+/// or any answer differs from the expected one.
+pub fn assert_examples(day: u8, manifest_dir: &str) {
+    let examples = load_examples(manifest_dir)
+        .unwrap_or_else(|err| panic!("Could not load examples for day {}: {:?}", day, err));
+    assert!(
+        !examples.is_empty(),
+        "No examples found in {}/{}",
+        manifest_dir,
+        EXAMPLES_DIR
+    );
+
+    let solutions: Vec<_> = registry::solutions()
+        .into_iter()
+        .filter(|entry| entry.day == day)
+        .collect();
+    assert!(
+        !solutions.is_empty(),
+        "No solutions registered for day {}",
+        day
+    );
+
+    let mut failures = vec![];
+    for example in examples.iter() {
+        for entry in solutions.iter() {
+            let Some(expected) = example.expected.get(entry.part) else {
+                continue;
+            };
+            match entry
+                .run(&example.input)
+                .map(|answer| answer.value.to_string())
+            {
+                Ok(actual) if actual == expected => {}
+                Ok(actual) => failures.push(format!(
+                    "{} on {}: expected {:?}, got {:?}",
+                    entry.name(),
+                    example.input_path.display(),
+                    expected,
+                    actual
+                )),
+                Err(err) => failures.push(format!(
+                    "{} on {}: failed with {:?}",
+                    entry.name(),
+                    example.input_path.display(),
+                    err
+                )),
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
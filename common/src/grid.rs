@@ -0,0 +1,441 @@
+//! Rectangular 2D grid, i.e. a character map from the puzzle input.
+//! Points are `(x, y)` with `x` the column and `y` the row, `(0, 0)` being the top left.
+
+use std::{
+    fmt,
+    ops::{Add, Index, IndexMut, Mul, Sub},
+};
+
+use crate::{
+    errors::{AdventErrors, AdventResult},
+    parse::{lines, Span},
+};
+
+/// Position in a grid, or an offset between positions
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const UP: Point = Point::new(0, -1);
+    pub const DOWN: Point = Point::new(0, 1);
+    pub const LEFT: Point = Point::new(-1, 0);
+    pub const RIGHT: Point = Point::new(1, 0);
+
+    /// Offsets to the 4 orthogonal neighbours, clockwise from up
+    pub const ORTHOGONAL: [Point; 4] = [Point::UP, Point::RIGHT, Point::DOWN, Point::LEFT];
+
+    /// Offsets to all 8 neighbours, clockwise from up
+    pub const ALL_DIRECTIONS: [Point; 8] = [
+        Point::UP,
+        Point::new(1, -1),
+        Point::RIGHT,
+        Point::new(1, 1),
+        Point::DOWN,
+        Point::new(-1, 1),
+        Point::LEFT,
+        Point::new(-1, -1),
+    ];
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan_distance(&self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+    fn mul(self, factor: i64) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// Rectangular grid of cells stored row by row
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Grid of `width` x `height` cells, all set to `fill`
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// # Return
+    /// An error when the rows are not all the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> AdventResult<Self> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        if let Some(idx) = rows.iter().position(|row| row.len() != width) {
+            return Err(AdventErrors::AdventError(format!(
+                "Row {} has {} cells, expected {}",
+                idx,
+                rows[idx].len(),
+                width
+            )));
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses one cell per character, skipping blank lines at the end of the input
+    ///
+    /// # Param
+    /// * `parse_cell` - Converts a character to a cell, returning `None` if it is not valid
+    pub fn parse_with(
+        input: &str,
+        mut parse_cell: impl FnMut(char) -> Option<T>,
+    ) -> AdventResult<Self> {
+        let lines: Vec<Span> = lines(input.trim_end()).collect();
+        let width = lines.first().map_or(0, |line| line.text.chars().count());
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for line in lines.iter() {
+            let num_columns = line.text.chars().count();
+            if num_columns != width {
+                return Err(
+                    line.error(format!("Expected {} columns, found {}", width, num_columns))
+                );
+            }
+            for (cell, cell_span) in line.char_spans() {
+                let cell = parse_cell(cell)
+                    .ok_or_else(|| cell_span.error("Unexpected character in grid"))?;
+                cells.push(cell);
+            }
+        }
+
+        Ok(Self {
+            width,
+            height: lines.len(),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn offset(&self, point: Point) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.y as usize * self.width + point.x as usize)
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (0..self.width as i64).contains(&point.x) && (0..self.height as i64).contains(&point.y)
+    }
+
+    /// # Return
+    /// `None` when `point` is outside the grid
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.offset(point).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.offset(point).map(|offset| &mut self.cells[offset])
+    }
+
+    /// Every point of the grid, row by row
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.height as i64)
+            .flat_map(move |y| (0..self.width as i64).map(move |x| Point::new(x, y)))
+    }
+
+    /// Every cell with its point, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// Points of the grid reached from `point` by each offset in `offsets`
+    fn neighbours<'a>(
+        &'a self,
+        point: Point,
+        offsets: &'a [Point],
+    ) -> impl Iterator<Item = Point> + 'a {
+        offsets
+            .iter()
+            .map(move |offset| point + *offset)
+            .filter(move |neighbour| self.contains(*neighbour))
+    }
+
+    /// Orthogonal neighbours of `point` inside the grid
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &Point::ORTHOGONAL)
+    }
+
+    /// Orthogonal and diagonal neighbours of `point` inside the grid
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &Point::ALL_DIRECTIONS)
+    }
+
+    /// Cells from `start` (included) stepping by `step` until leaving the grid,
+    /// i.e. `Point::new(1, 1)` walks a diagonal
+    pub fn walk(&self, start: Point, step: Point) -> impl Iterator<Item = (Point, &T)> + '_ {
+        let mut next = self.contains(start).then_some(start);
+        std::iter::from_fn(move || {
+            let point = next?;
+            // A zero step would never leave the grid, so it only yields `start`
+            next =
+                Some(point + step).filter(|next| step != Point::default() && self.contains(*next));
+            Some((point, &self[point]))
+        })
+    }
+
+    /// Cells of row `y`, left to right
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> + '_ {
+        self.walk(Point::new(0, y as i64), Point::RIGHT)
+            .map(|(_, cell)| cell)
+    }
+
+    /// Cells of column `x`, top to bottom
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        self.walk(Point::new(x as i64, 0), Point::DOWN)
+            .map(|(_, cell)| cell)
+    }
+
+    /// Builds a new grid of the same shape from every cell
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(&mut f).collect(),
+        }
+    }
+
+    /// Grid of `width` x `height` cells taken from `self` at the point returned by `source`
+    fn rearranged(&self, width: usize, height: usize, source: impl Fn(i64, i64) -> Point) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..height as i64)
+            .flat_map(|y| (0..width as i64).map(move |x| (x, y)))
+            .map(|(x, y)| self[source(x, y)].clone())
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Swaps rows and columns
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearranged(self.height, self.width, |x, y| Point::new(y, x))
+    }
+
+    /// Rotates a quarter turn clockwise
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let last_row = self.height as i64 - 1;
+        self.rearranged(self.height, self.width, |x, y| Point::new(y, last_row - x))
+    }
+
+    /// Rotates a quarter turn counter-clockwise
+    pub fn rotate_counter_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let last_column = self.width as i64 - 1;
+        self.rearranged(self.height, self.width, |x, y| {
+            Point::new(last_column - y, x)
+        })
+    }
+}
+
+impl Grid<char> {
+    /// Grid of the characters of the input
+    pub fn parse_chars(input: &str) -> AdventResult<Self> {
+        Self::parse_with(input, Some)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    /// # Panics
+    /// When `point` is outside the grid. Use `get` to check the bounds instead
+    fn index(&self, point: Point) -> &T {
+        match self.get(point) {
+            Some(cell) => cell,
+            None => panic!(
+                "Point {} is outside the {}x{} grid",
+                point, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(point) {
+            Some(cell) => cell,
+            None => panic!("Point {} is outside the {}x{} grid", point, width, height),
+        }
+    }
+}
+
+/// Prints every row on its own line, with the cells next to each other
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in self.row(y) {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    const EXAMPLE: &str = "abc\ndef\n";
+
+    #[test]
+    fn test_parse_and_index() {
+        let grid = Grid::parse_chars(EXAMPLE).expect("Grid should parse");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], 'f');
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+        assert_eq!(grid.to_string(), "abc\ndef");
+
+        let digits = Grid::parse_with("12\n34", |cell| cell.to_digit(10)).expect("Digits");
+        assert_eq!(digits.column(1).copied().collect::<Vec<_>>(), vec![2, 4]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        match Grid::parse_chars("abc\nde") {
+            Err(AdventErrors::InvalidInput { location, .. }) => assert_eq!(location.line, 2),
+            res => panic!("Unexpected result {:?}", res),
+        }
+
+        match Grid::parse_with("12\n3x", |cell| cell.to_digit(10)) {
+            Err(AdventErrors::InvalidInput { location, .. }) => {
+                assert_eq!((location.line, location.column), (2, 2));
+            }
+            res => panic!("Unexpected result {:?}", res),
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the 3x2 grid")]
+    fn test_index_out_of_bounds() {
+        let grid = Grid::parse_chars(EXAMPLE).expect("Grid should parse");
+        let _ = grid[Point::new(0, 2)];
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::new(3, 3, 0u8);
+        let corner: Vec<Point> = grid.neighbours4(Point::new(0, 0)).collect();
+        assert_eq!(corner, vec![Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(grid.neighbours4(Point::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8(Point::new(2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn test_walks() {
+        let grid = Grid::parse_chars("abc\ndef\nghi").expect("Grid should parse");
+        let row: String = grid.row(1).collect();
+        assert_eq!(row, "def");
+
+        let diagonal: String = grid
+            .walk(Point::new(0, 0), Point::new(1, 1))
+            .map(|(_, cell)| cell)
+            .collect();
+        assert_eq!(diagonal, "aei");
+
+        let anti_diagonal: String = grid
+            .walk(Point::new(2, 0), Point::new(-1, 1))
+            .map(|(_, cell)| cell)
+            .collect();
+        assert_eq!(anti_diagonal, "ceg");
+
+        assert_eq!(grid.walk(Point::new(1, 1), Point::default()).count(), 1);
+    }
+
+    #[test]
+    fn test_transpose_and_rotate() {
+        let grid = Grid::parse_chars(EXAMPLE).expect("Grid should parse");
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counter_clockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(
+            grid.rotate_clockwise().rotate_counter_clockwise(),
+            grid.clone()
+        );
+
+        let upper = grid.map(|cell| cell.to_ascii_uppercase());
+        assert_eq!(upper.to_string(), "ABC\nDEF");
+    }
+
+    #[test]
+    fn test_from_rows() {
+        let grid = Grid::from_rows(vec![vec![1, 2], vec![3, 4]]).expect("Rows are even");
+        assert_eq!(grid[Point::new(0, 1)], 3);
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_err());
+    }
+
+    #[test]
+    fn test_manhattan_distance() {
+        let distance = Point::new(1, -2).manhattan_distance(Point::new(-3, 4));
+        assert_eq!(distance, 10);
+        assert_eq!(Point::new(1, 2) + Point::RIGHT * 2, Point::new(3, 2));
+    }
+}
@@ -0,0 +1,313 @@
+//! Locates the input file for a given day.
+//!
+//! Locations are searched in order:
+//! 1. An explicit path given on the command line, or `-` for stdin
+//! 2. The `ADVENT_INPUT_DIR` environment variable
+//! 3. The `input_dir` of the configuration file
+//! 4. The day crate's manifest directory
+//! 5. The current directory
+
+use std::{
+    env, fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::OnceLock,
+};
+
+use crate::errors::{AdventErrors, AdventResult};
+
+/// Environment variable pointing at a directory with a `dayN/` folder per day
+pub const INPUT_DIR_ENV_VAR: &str = "ADVENT_INPUT_DIR";
+
+/// File name used by days that do not specify their own
+pub const DEFAULT_INPUT_FILE: &str = "input.txt";
+
+/// Where the user asked for the input to be read from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    /// Standard input, given as `-`
+    Stdin,
+    /// A file, either absolute or relative to the current directory
+    File(PathBuf),
+}
+
+/// Standard input can only be read once, so every reader shares the first read
+static STDIN_CONTENTS: OnceLock<String> = OnceLock::new();
+
+impl InputSource {
+    /// Name used for the source in error messages
+    pub fn label(&self) -> &Path {
+        match self {
+            InputSource::Stdin => Path::new("<stdin>"),
+            InputSource::File(path) => path,
+        }
+    }
+
+    /// Reads the whole input.
+    /// Standard input is read on first use and the same contents returned afterwards.
+    pub fn read_to_string(&self) -> AdventResult<String> {
+        match self {
+            InputSource::File(path) => Ok(fs::read_to_string(path)?),
+            InputSource::Stdin => {
+                if let Some(contents) = STDIN_CONTENTS.get() {
+                    return Ok(contents.clone());
+                }
+                let mut contents = String::new();
+                io::stdin().read_to_string(&mut contents)?;
+                Ok(STDIN_CONTENTS.get_or_init(|| contents).clone())
+            }
+        }
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = AdventErrors;
+
+    fn from_str(input: &str) -> AdventResult<InputSource> {
+        match input {
+            "" => Err(AdventErrors::AdventError(
+                "The input path cannot be empty".to_string(),
+            )),
+            "-" => Ok(InputSource::Stdin),
+            path => Ok(InputSource::File(PathBuf::from(path))),
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label().display())
+    }
+}
+
+/// Resolves where a day's input lives
+#[derive(Clone, Debug)]
+pub struct InputLocator {
+    day: u8,
+    file_name: PathBuf,
+    explicit: Option<InputSource>,
+    env_dir: Option<PathBuf>,
+    config_dir: Option<PathBuf>,
+    manifest_dir: Option<PathBuf>,
+    current_dir: Option<PathBuf>,
+}
+
+impl InputLocator {
+    /// Creates a locator for `file_name` of `day`, reading the environment.
+    /// The configured `input_dir` is only searched once given with `config_dir`.
+    pub fn new(day: u8, file_name: impl Into<PathBuf>) -> Self {
+        let env_dir = env::var_os(INPUT_DIR_ENV_VAR)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from);
+
+        Self {
+            day,
+            file_name: file_name.into(),
+            explicit: None,
+            env_dir,
+            config_dir: None,
+            manifest_dir: None,
+            current_dir: env::current_dir().ok(),
+        }
+    }
+
+    /// Input given by the user. When set, no other location is searched.
+    pub fn explicit(mut self, source: Option<InputSource>) -> Self {
+        self.explicit = source;
+        self
+    }
+
+    /// The `input_dir` of the configuration, holding a `dayN/` folder per day
+    pub fn config_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.config_dir = dir;
+        self
+    }
+
+    /// Directory of the day's crate, normally `env!("CARGO_MANIFEST_DIR")`
+    pub fn manifest_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.manifest_dir = Some(dir.into());
+        self
+    }
+
+    /// Every location that will be checked, in priority order.
+    /// Empty when reading from stdin.
+    pub fn candidates(&self) -> Vec<PathBuf> {
+        match &self.explicit {
+            Some(InputSource::Stdin) => return vec![],
+            // Relative paths are taken from the current directory, not the day's directory
+            Some(InputSource::File(path)) => {
+                return vec![match &self.current_dir {
+                    Some(dir) => dir.join(path),
+                    None => path.to_owned(),
+                }]
+            }
+            None => {}
+        }
+
+        let day_dir = format!("day{}", self.day);
+        let in_day_dir = |dir: &Path| dir.join(&day_dir).join(&self.file_name);
+
+        let mut candidates = vec![];
+        candidates.extend(self.env_dir.as_deref().map(in_day_dir));
+        candidates.extend(self.config_dir.as_deref().map(in_day_dir));
+        candidates.extend(
+            self.manifest_dir
+                .as_ref()
+                .map(|dir| dir.join(&self.file_name)),
+        );
+        candidates.extend(self.current_dir.as_deref().map(in_day_dir));
+        candidates
+    }
+
+    /// Finds the first candidate that exists
+    ///
+    /// # Return
+    /// Where to read the input from, or an error listing every location tried
+    pub fn locate(&self) -> AdventResult<InputSource> {
+        if let Some(InputSource::Stdin) = self.explicit {
+            return Ok(InputSource::Stdin);
+        }
+        let candidates = self.candidates();
+        match candidates.iter().find(|path| path.is_file()) {
+            Some(path) => Ok(InputSource::File(path.to_owned())),
+            None => Err(AdventErrors::InputNotFound {
+                day: self.day,
+                tried: candidates,
+            }),
+        }
+    }
+
+    /// Where a missing input should be saved: the location searched first.
+    /// `None` when reading from stdin.
+    pub fn destination(&self) -> Option<PathBuf> {
+        self.candidates().into_iter().next()
+    }
+
+    /// Locates and reads the input
+    pub fn read_to_string(&self) -> AdventResult<String> {
+        self.locate()?.read_to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    fn empty_locator(day: u8) -> InputLocator {
+        InputLocator {
+            day,
+            file_name: PathBuf::from(DEFAULT_INPUT_FILE),
+            explicit: None,
+            env_dir: None,
+            config_dir: None,
+            manifest_dir: None,
+            current_dir: None,
+        }
+    }
+
+    #[test]
+    fn test_candidates_order() {
+        let locator = InputLocator {
+            env_dir: Some(PathBuf::from("/env")),
+            config_dir: Some(PathBuf::from("/config")),
+            manifest_dir: Some(PathBuf::from("/manifest")),
+            current_dir: Some(PathBuf::from("/cwd")),
+            ..empty_locator(3)
+        };
+
+        let expected: Vec<PathBuf> = [
+            "/env/day3/input.txt",
+            "/config/day3/input.txt",
+            "/manifest/input.txt",
+            "/cwd/day3/input.txt",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+        assert_eq!(locator.candidates(), expected);
+        assert_eq!(
+            locator.destination(),
+            Some(PathBuf::from("/env/day3/input.txt"))
+        );
+    }
+
+    #[test]
+    fn test_config_dir_given_by_caller() {
+        let unconfigured = InputLocator::new(3, DEFAULT_INPUT_FILE);
+        assert_eq!(unconfigured.config_dir, None);
+
+        let configured = unconfigured.config_dir(Some(PathBuf::from("/config")));
+        assert!(configured
+            .candidates()
+            .contains(&PathBuf::from("/config/day3/input.txt")));
+    }
+
+    #[test]
+    fn test_explicit_path_wins() {
+        let locator = InputLocator {
+            manifest_dir: Some(PathBuf::from("/manifest")),
+            ..empty_locator(1)
+        }
+        .explicit(Some(InputSource::File(PathBuf::from("/tmp/other.txt"))));
+
+        assert_eq!(locator.candidates(), vec![PathBuf::from("/tmp/other.txt")]);
+    }
+
+    #[test]
+    fn test_explicit_sources() {
+        let relative = InputLocator {
+            manifest_dir: Some(PathBuf::from("/manifest")),
+            current_dir: Some(PathBuf::from("/cwd")),
+            ..empty_locator(1)
+        }
+        .explicit(Some("inputs/mine.txt".parse().expect("A path")));
+        assert_eq!(
+            relative.candidates(),
+            vec![PathBuf::from("/cwd/inputs/mine.txt")]
+        );
+
+        let stdin = empty_locator(1).explicit(Some("-".parse().expect("Stdin")));
+        assert!(stdin.candidates().is_empty());
+        assert_eq!(stdin.destination(), None);
+        assert_eq!(stdin.locate().expect("Stdin"), InputSource::Stdin);
+        assert_eq!(InputSource::Stdin.to_string(), "<stdin>");
+        assert!("".parse::<InputSource>().is_err());
+    }
+
+    #[test]
+    fn test_locate_finds_first_existing() {
+        let dir = tempfile::tempdir().expect("Should create temp dir");
+        fs::create_dir(dir.path().join("day2")).expect("Should create day dir");
+        fs::write(dir.path().join("day2").join("input.txt"), "A Y\n").expect("Should write");
+
+        let locator = InputLocator {
+            env_dir: Some(dir.path().join("missing")),
+            config_dir: Some(dir.path().to_owned()),
+            ..empty_locator(2)
+        };
+        let found = locator.locate().expect("Input should be found");
+        assert_eq!(
+            found,
+            InputSource::File(dir.path().join("day2").join("input.txt"))
+        );
+    }
+
+    #[test]
+    fn test_locate_lists_tried_paths() {
+        let locator = InputLocator {
+            env_dir: Some(PathBuf::from("/does/not/exist")),
+            manifest_dir: Some(PathBuf::from("/also/missing")),
+            ..empty_locator(4)
+        };
+
+        match locator.locate() {
+            Err(AdventErrors::InputNotFound { day, tried }) => {
+                assert_eq!(day, 4);
+                assert_eq!(tried.len(), 2);
+            }
+            other => panic!("Expected InputNotFound, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,370 @@
+//! Inclusive integer intervals and normalized sets of them, i.e. the section ranges of day 4
+
+use std::fmt;
+
+/// Integer types usable as the bounds of an `Interval`
+pub trait IntervalBound: Copy + Ord + fmt::Debug + fmt::Display {
+    /// The next value, `None` at the maximum
+    fn successor(self) -> Option<Self>;
+    /// The previous value, `None` at the minimum
+    fn predecessor(self) -> Option<Self>;
+    /// Number of values from `start` to `end`, both included
+    fn count_between(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_interval_bound {
+    ($($bound:ty),*) => {
+        $(
+            impl IntervalBound for $bound {
+                fn successor(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn predecessor(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn count_between(start: Self, end: Self) -> u128 {
+                    (end as i128 - start as i128 + 1) as u128
+                }
+            }
+        )*
+    };
+}
+
+impl_interval_bound!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Interval including both `start` and `end`.
+/// Only built through `new`, `try_new` or `single`, so `start` is never after `end`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval<T> {
+    start: T,
+    end: T,
+}
+
+impl<T: IntervalBound> Interval<T> {
+    /// # Panics
+    /// When `start` is after `end`. Use `try_new` for bounds from the input
+    pub fn new(start: T, end: T) -> Self {
+        match Self::try_new(start, end) {
+            Some(interval) => interval,
+            None => panic!("Interval start {} is after its end {}", start, end),
+        }
+    }
+
+    /// # Return
+    /// `None` when `start` is after `end`
+    pub fn try_new(start: T, end: T) -> Option<Self> {
+        (start <= end).then_some(Self { start, end })
+    }
+
+    /// Interval holding the single value `value`
+    pub fn single(value: T) -> Self {
+        Self {
+            start: value,
+            end: value,
+        }
+    }
+
+    /// First value of the interval
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    /// Last value of the interval
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    /// Number of values in the interval
+    pub fn len(&self) -> u128 {
+        T::count_between(self.start, self.end)
+    }
+
+    /// Always false, as an interval holds at least one value
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Whether every value of `other` is in `self`
+    pub fn contains_interval(&self, other: &Interval<T>) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Whether the intervals share at least one value
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Values in both intervals
+    pub fn intersection(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        Self::try_new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// Whether the intervals overlap or one starts right after the other ends
+    fn touches(&self, other: &Interval<T>) -> bool {
+        let (first, second) = if self.start <= other.start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        first
+            .end
+            .successor()
+            .is_none_or(|after_end| second.start <= after_end)
+    }
+
+    /// Single interval covering both intervals
+    ///
+    /// # Return
+    /// `None` when there is a gap between them
+    pub fn union(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        self.touches(other).then(|| Interval {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// Set of values stored as sorted, disjoint and non-adjacent intervals
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: IntervalBound> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { intervals: vec![] }
+    }
+
+    /// Sorts the intervals and merges the ones that overlap or touch
+    fn normalize(&mut self) {
+        self.intervals.sort();
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(self.intervals.len());
+        for interval in self.intervals.drain(..) {
+            match merged.last_mut() {
+                Some(last) if last.touches(&interval) => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        self.intervals = merged;
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        self.intervals.push(interval);
+        self.normalize();
+    }
+
+    /// The normalized intervals, in order
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Total number of values covered by the set
+    pub fn len(&self) -> u128 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.find(value).is_some()
+    }
+
+    /// Whether every value of `interval` is in the set
+    pub fn contains_interval(&self, interval: &Interval<T>) -> bool {
+        self.find(interval.start)
+            .is_some_and(|containing| containing.contains_interval(interval))
+    }
+
+    /// The interval of the set holding `value`
+    fn find(&self, value: T) -> Option<&Interval<T>> {
+        let idx = self
+            .intervals
+            .partition_point(|interval| interval.end < value);
+        self.intervals
+            .get(idx)
+            .filter(|interval| interval.contains(value))
+    }
+
+    /// Values in either set
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        self.intervals
+            .iter()
+            .chain(other.intervals.iter())
+            .copied()
+            .collect()
+    }
+
+    /// Values in both sets
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = vec![];
+        let (mut left, mut right) = (0, 0);
+        while let (Some(a), Some(b)) = (self.intervals.get(left), other.intervals.get(right)) {
+            if let Some(common) = a.intersection(b) {
+                intervals.push(common);
+            }
+            if a.end < b.end {
+                left += 1;
+            } else {
+                right += 1;
+            }
+        }
+        // Intersections of normalized sets are already normalized
+        IntervalSet { intervals }
+    }
+
+    /// Values in `self` but not in `other`
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = vec![];
+        for interval in self.intervals.iter() {
+            let mut start = Some(interval.start);
+            let overlapping = other
+                .intervals
+                .iter()
+                .filter(|removed| removed.overlaps(interval));
+            for removed in overlapping {
+                let Some(current) = start else {
+                    break;
+                };
+                if current < removed.start {
+                    intervals.push(Interval::new(
+                        current,
+                        removed.start.predecessor().unwrap_or(current),
+                    ));
+                }
+                start = removed.end.successor().filter(|next| *next <= interval.end);
+            }
+            if let Some(start) = start {
+                intervals.push(Interval::new(start, interval.end));
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    /// Values missing between the first and last interval of the set
+    pub fn gaps(&self) -> IntervalSet<T> {
+        let intervals = self
+            .intervals
+            .windows(2)
+            .filter_map(|pair| {
+                // Normalized neighbours always have at least one value between them
+                Interval::try_new(pair[0].end.successor()?, pair[1].start.predecessor()?)
+            })
+            .collect();
+        IntervalSet { intervals }
+    }
+}
+
+impl<T: IntervalBound> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = Self {
+            intervals: iter.into_iter().collect(),
+        };
+        set.normalize();
+        set
+    }
+}
+
+impl<T: IntervalBound> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Self {
+            intervals: vec![interval],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect()
+    }
+
+    #[test]
+    fn test_interval_queries() {
+        let interval = Interval::new(2, 6);
+        assert_eq!((interval.start(), interval.end()), (2, 6));
+        assert_eq!(interval.len(), 5);
+        assert!(interval.contains(6) && !interval.contains(7));
+        assert!(interval.contains_interval(&Interval::new(3, 6)));
+        assert!(!interval.contains_interval(&Interval::new(3, 7)));
+        assert!(interval.overlaps(&Interval::new(6, 9)));
+        assert!(!interval.overlaps(&Interval::new(7, 9)));
+        assert_eq!(
+            interval.intersection(&Interval::new(4, 8)),
+            Some(Interval::new(4, 6))
+        );
+        assert_eq!(interval.intersection(&Interval::new(7, 8)), None);
+        assert_eq!(
+            interval.union(&Interval::new(7, 8)),
+            Some(Interval::new(2, 8))
+        );
+        assert_eq!(interval.union(&Interval::new(8, 9)), None);
+        assert_eq!(Interval::try_new(3u8, 2), None);
+        assert_eq!(Interval::new(0u8, u8::MAX).len(), 256);
+    }
+
+    #[test]
+    fn test_set_normalized() {
+        let merged = set(&[(5, 7), (1, 2), (3, 3), (10, 12), (11, 15)]);
+        assert_eq!(
+            merged.intervals(),
+            &[
+                Interval::new(1, 3),
+                Interval::new(5, 7),
+                Interval::new(10, 15)
+            ]
+        );
+        assert_eq!(merged.len(), 3 + 3 + 6);
+        assert!(merged.contains(6) && !merged.contains(4) && !merged.contains(16));
+        assert!(merged.contains_interval(&Interval::new(11, 15)));
+        assert!(!merged.contains_interval(&Interval::new(3, 5)));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[(1, 5), (10, 15)]);
+        let b = set(&[(4, 11), (20, 20)]);
+
+        assert_eq!(a.union(&b), set(&[(1, 15), (20, 20)]));
+        assert_eq!(a.intersection(&b), set(&[(4, 5), (10, 11)]));
+        assert_eq!(a.difference(&b), set(&[(1, 3), (12, 15)]));
+        assert_eq!(b.difference(&a), set(&[(6, 9), (20, 20)]));
+        assert_eq!(a.difference(&a), IntervalSet::new());
+        assert_eq!(a.gaps(), set(&[(6, 9)]));
+    }
+
+    #[test]
+    fn test_set_at_type_limits() {
+        let full = IntervalSet::from(Interval::new(0u8, u8::MAX));
+        let middle = IntervalSet::from(Interval::new(10u8, 20));
+        assert_eq!(
+            full.difference(&middle).intervals(),
+            &[Interval::new(0, 9), Interval::new(21, u8::MAX)]
+        );
+        assert_eq!(full.difference(&full), IntervalSet::new());
+
+        let touching: IntervalSet<u8> = [Interval::new(250, u8::MAX), Interval::new(0, 249)]
+            .into_iter()
+            .collect();
+        assert_eq!(touching, full);
+    }
+}
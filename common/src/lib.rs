@@ -1,3 +1,19 @@
+pub mod alphabet;
+pub mod answers;
 pub mod command;
+pub mod config;
+pub mod diagnostics;
 pub mod errors;
-pub mod utils;
+pub mod fixtures;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod logging;
+pub mod parse;
+pub mod registry;
+pub mod search;
+
+// Re-exported for use by `register_solution!`
+pub use inventory;
+// Re-exported so solutions can log without their own dependency
+pub use tracing;
@@ -0,0 +1,44 @@
+//! Leveled logging to stderr, so stdout only carries answers.
+//! Solutions log through the re-exported `advent_common::tracing` macros.
+
+use std::io::{self, IsTerminal};
+
+use tracing::level_filters::LevelFilter;
+
+use crate::config::Verbosity;
+
+/// Most detailed level logged at `verbosity`
+pub fn max_level(verbosity: Verbosity) -> LevelFilter {
+    match verbosity {
+        Verbosity::Quiet => LevelFilter::ERROR,
+        Verbosity::Normal => LevelFilter::WARN,
+        Verbosity::Verbose => LevelFilter::INFO,
+        Verbosity::Debug => LevelFilter::DEBUG,
+        Verbosity::Trace => LevelFilter::TRACE,
+    }
+}
+
+/// Sends log events up to `verbosity` to stderr.
+/// Only the first call has an effect, so tests and binaries can both call it.
+pub fn init(verbosity: Verbosity) {
+    let _ = tracing_subscriber::fmt()
+        .with_writer(io::stderr)
+        .with_max_level(max_level(verbosity))
+        .without_time()
+        .with_ansi(io::stderr().is_terminal())
+        .with_target(verbosity >= Verbosity::Debug)
+        .try_init();
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_levels_grow_with_verbosity() {
+        assert_eq!(max_level(Verbosity::default()), LevelFilter::WARN);
+        assert!(max_level(Verbosity::Quiet) < max_level(Verbosity::Normal));
+        assert!(max_level(Verbosity::Debug) < max_level(Verbosity::Trace));
+    }
+}
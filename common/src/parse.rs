@@ -0,0 +1,275 @@
+//! Building blocks for parsing puzzle input.
+//! Every piece of text is handed out as a `Span` that remembers the line it came from,
+//! so failures point at the offending text.
+
+use std::{fmt::Display, str::FromStr};
+
+use crate::errors::{AdventErrors, AdventResult, InputSpan};
+
+/// A slice of one line of the input
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Span<'a> {
+    /// 1-based line number
+    pub line_number: usize,
+    /// The full line `text` is a slice of
+    pub line: &'a str,
+    pub text: &'a str,
+}
+
+impl<'a> Span<'a> {
+    /// Span covering the whole of `line`
+    pub fn new(line_number: usize, line: &'a str) -> Self {
+        Self {
+            line_number,
+            line,
+            text: line,
+        }
+    }
+
+    fn slice(&self, text: &'a str) -> Self {
+        Self { text, ..*self }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Owned location of the span, for use in errors
+    pub fn location(&self) -> InputSpan {
+        InputSpan::new(self.line_number, self.line, self.text)
+    }
+
+    /// Error pointing at this span
+    pub fn error(&self, message: impl Into<String>) -> AdventErrors {
+        AdventErrors::invalid_input(self.location(), message)
+    }
+
+    /// Parses the span via `FromStr`, pointing at it on failure
+    pub fn parse<T>(&self) -> AdventResult<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.text
+            .parse::<T>()
+            .map_err(|err| self.error(err.to_string()))
+    }
+
+    /// The span with surrounding whitespace removed
+    pub fn trim(&self) -> Self {
+        self.slice(self.text.trim())
+    }
+
+    /// Splits the span into two at byte index `mid`
+    ///
+    /// # Return
+    /// An error pointing at the span when `mid` is past its end or inside a character
+    pub fn split_at(&self, mid: usize) -> AdventResult<(Self, Self)> {
+        let (first, second) = self
+            .text
+            .split_at_checked(mid)
+            .ok_or_else(|| self.error(format!("Cannot split at byte {}", mid)))?;
+        Ok((self.slice(first), self.slice(second)))
+    }
+
+    /// Every character of the span with its own span
+    pub fn char_spans(&self) -> impl Iterator<Item = (char, Span<'a>)> + 'a {
+        let span = *self;
+        self.text
+            .char_indices()
+            .map(move |(idx, item)| (item, span.slice(&span.text[idx..idx + item.len_utf8()])))
+    }
+
+    /// Splits the span on every `delimiter`
+    pub fn split(&self, delimiter: &'a str) -> impl Iterator<Item = Span<'a>> + 'a {
+        let span = *self;
+        self.text.split(delimiter).map(move |text| span.slice(text))
+    }
+
+    /// Splits the span into exactly `N` fields
+    ///
+    /// # Return
+    /// An error pointing at the whole span when the number of fields differs
+    pub fn split_fixed<const N: usize>(&self, delimiter: &'a str) -> AdventResult<[Span<'a>; N]> {
+        let fields: Vec<Span<'a>> = self.split(delimiter).collect();
+        let num_fields = fields.len();
+        fields.try_into().map_err(|_| {
+            self.error(format!(
+                "Expected {} fields separated by `{}`, found {}",
+                N, delimiter, num_fields
+            ))
+        })
+    }
+
+    /// Every integer in the span, i.e. `[3, -4, 7]` from `move 3 from -4 to 7`.
+    /// A `-` directly before digits makes the integer negative, unless it follows a letter
+    /// or digit: `2-4` is the range of `2` and `4`.
+    pub fn integers<T>(&self) -> AdventResult<Vec<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let bytes = self.text.as_bytes();
+        let mut integers = vec![];
+        let mut idx = 0;
+        while idx < bytes.len() {
+            let negative = bytes[idx] == b'-'
+                && bytes.get(idx + 1).is_some_and(|byte| byte.is_ascii_digit())
+                && !(idx > 0 && bytes[idx - 1].is_ascii_alphanumeric());
+            if !negative && !bytes[idx].is_ascii_digit() {
+                idx += 1;
+                continue;
+            }
+
+            let start = idx;
+            idx += 1;
+            while idx < bytes.len() && bytes[idx].is_ascii_digit() {
+                idx += 1;
+            }
+            integers.push(self.slice(&self.text[start..idx]).parse::<T>()?);
+        }
+        Ok(integers)
+    }
+}
+
+impl Display for Span<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+/// Every line of the input, numbered from 1
+pub fn lines(input: &str) -> impl Iterator<Item = Span<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| Span::new(idx + 1, line))
+}
+
+/// Parses every line of the input with `parse_line`, stopping at the first error
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    parse_line: impl FnMut(Span<'a>) -> AdventResult<T>,
+) -> AdventResult<Vec<T>> {
+    lines(input).map(parse_line).collect()
+}
+
+/// Lines of the input grouped by the blank lines separating them.
+/// Consecutive blank lines do not produce empty groups.
+pub fn groups(input: &str) -> Vec<Vec<Span<'_>>> {
+    let mut groups = vec![];
+    let mut current = vec![];
+    for line in lines(input) {
+        if line.text.trim().is_empty() {
+            if !current.is_empty() {
+                groups.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        groups.push(current);
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_groups() {
+        let groups = groups("1\n2\n\n3\n\n\n4\n");
+        let texts: Vec<Vec<&str>> = groups
+            .iter()
+            .map(|group| group.iter().map(Span::as_str).collect())
+            .collect();
+        assert_eq!(texts, vec![vec!["1", "2"], vec!["3"], vec!["4"]]);
+        assert_eq!(groups[2][0].line_number, 7);
+    }
+
+    #[test]
+    fn test_split_fixed() {
+        let line = Span::new(4, "2-4,6-8");
+        let [first, second] = line.split_fixed::<2>(",").expect("Two fields");
+        let [start, end] = second.split_fixed::<2>("-").expect("Two fields");
+        assert_eq!(first.as_str(), "2-4");
+        assert_eq!(start.parse::<u32>().expect("Number"), 6);
+        assert_eq!(end.location().column, 7);
+
+        let (item, item_span) = second.char_spans().nth(2).expect("Three characters");
+        assert_eq!((item, item_span.location().column), ('8', 7));
+
+        let err = line.split_fixed::<3>(",").expect_err("Only two fields");
+        assert_eq!(
+            err.to_string(),
+            "line 4, column 1: Expected 3 fields separated by `,`, found 2 (found `2-4,6-8`)"
+        );
+    }
+
+    #[test]
+    fn test_split_at() {
+        let line = Span::new(1, "abcd");
+        let (first, second) = line.split_at(1).expect("Inside the span");
+        assert_eq!((first.as_str(), second.as_str()), ("a", "bcd"));
+        assert!(line.split_at(5).is_err());
+        assert!(Span::new(1, "aéb").split_at(2).is_err());
+    }
+
+    #[test]
+    fn test_parse_error_located() {
+        let [_, bad] = Span::new(2, "7 x1")
+            .split_fixed::<2>(" ")
+            .expect("Two fields");
+        match bad.parse::<u8>() {
+            Err(AdventErrors::InvalidInput { location, .. }) => {
+                assert_eq!((location.line, location.column), (2, 3));
+                assert_eq!(location.text, "x1");
+            }
+            res => panic!("Unexpected result {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_integers() {
+        let line = Span::new(1, "move 3 from -4 to 17, not-a-number -");
+        assert_eq!(line.integers::<i32>().expect("Integers"), vec![3, -4, 17]);
+
+        let ranges = Span::new(1, "2-4,6-8");
+        assert_eq!(
+            ranges.integers::<u32>().expect("Integers"),
+            vec![2, 4, 6, 8]
+        );
+        assert_eq!(
+            ranges.integers::<i32>().expect("Integers"),
+            vec![2, 4, 6, 8]
+        );
+        let bounds = Span::new(1, "x=-3..5");
+        assert_eq!(bounds.integers::<i32>().expect("Integers"), vec![-3, 5]);
+
+        let err = Span::new(1, "x 300")
+            .integers::<u8>()
+            .expect_err("Too large");
+        match err {
+            AdventErrors::InvalidInput { location, .. } => assert_eq!(location.column, 3),
+            err => panic!("Unexpected error {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let parsed = parse_lines("1\n2\n3", |line| line.parse::<u8>()).expect("Numbers");
+        assert_eq!(parsed, vec![1, 2, 3]);
+        assert!(parse_lines("1\nx", |line| line.parse::<u8>()).is_err());
+    }
+}
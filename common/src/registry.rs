@@ -0,0 +1,286 @@
+//! Registry of every solution linked into the binary.
+//! Day crates add themselves with `register_solution!`, so the CLI never needs editing.
+
+use std::{any::Any, fmt, path::PathBuf, str::FromStr};
+
+use crate::{
+    command::{AdventSolution, Answer},
+    errors::{AdventErrors, AdventResult},
+    input::{InputLocator, InputSource},
+};
+
+/// Which half of a day's puzzle a solution answers
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    A,
+    B,
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::A => write!(f, "a"),
+            Part::B => write!(f, "b"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = AdventErrors;
+    fn from_str(input: &str) -> AdventResult<Part> {
+        match input.to_lowercase().as_str() {
+            "a" | "1" => Ok(Part::A),
+            "b" | "2" => Ok(Part::B),
+            _ => Err(AdventErrors::AdventError(format!(
+                "Could not map {} -> Part (expected a, b, 1 or 2)",
+                input
+            ))),
+        }
+    }
+}
+
+/// Inclusive range of days selected on the command line, i.e. `3` or `3..5`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DaySelection {
+    pub first: u8,
+    pub last: u8,
+}
+
+impl DaySelection {
+    pub fn contains(&self, day: u8) -> bool {
+        self.first <= day && day <= self.last
+    }
+}
+
+impl fmt::Display for DaySelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.first == self.last {
+            write!(f, "{}", self.first)
+        } else {
+            write!(f, "{}..{}", self.first, self.last)
+        }
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = AdventErrors;
+    fn from_str(input: &str) -> AdventResult<DaySelection> {
+        let parse_day = |day: &str| {
+            day.trim().parse::<u8>().map_err(|_| {
+                AdventErrors::AdventError(format!("Could not map {} -> day number", input))
+            })
+        };
+
+        let (first, last) = match input.split_once("..") {
+            Some((first, last)) => {
+                let last = last.strip_prefix('=').unwrap_or(last);
+                (parse_day(first)?, parse_day(last)?)
+            }
+            None => {
+                let day = parse_day(input)?;
+                (day, day)
+            }
+        };
+
+        if first > last {
+            return Err(AdventErrors::AdventError(format!(
+                "Day range {} is empty",
+                input
+            )));
+        }
+        Ok(Self { first, last })
+    }
+}
+
+/// A day's parsed input, type-erased so every day can share one registry
+pub type ParsedInput = Box<dyn Any>;
+
+/// Metadata and entry points of a single registered solution.
+/// Both parts of a day share the same `parse`.
+pub struct SolutionEntry {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    /// Name of the input file searched for by the locator
+    pub input_file: &'static str,
+    /// Directory of the day's crate, used as a fallback input location
+    pub manifest_dir: &'static str,
+    /// Parses the puzzle input for the day
+    pub parse: fn(&str) -> AdventResult<ParsedInput>,
+    /// Solves this part over the output of `parse`
+    pub solve: fn(&ParsedInput) -> AdventResult<Answer>,
+}
+
+impl SolutionEntry {
+    /// Short name of the solution, i.e. `day3b`
+    pub fn name(&self) -> String {
+        format!("day{}{}", self.day, self.part)
+    }
+
+    /// Parses `input` and solves this part
+    pub fn run(&self, input: &str) -> AdventResult<Answer> {
+        (self.solve)(&(self.parse)(input)?)
+    }
+
+    /// Whether `other` is the other part of the same day, so can reuse its parsed input
+    pub fn shares_input_with(&self, other: &SolutionEntry) -> bool {
+        self.year == other.year && self.day == other.day && self.input_file == other.input_file
+    }
+
+    /// Locator for this solution's input
+    ///
+    /// # Param
+    /// * `config_dir` - The `input_dir` of the configuration, if any
+    pub fn locator(
+        &self,
+        explicit: Option<InputSource>,
+        config_dir: Option<PathBuf>,
+    ) -> InputLocator {
+        InputLocator::new(self.day, self.input_file)
+            .explicit(explicit)
+            .config_dir(config_dir)
+            .manifest_dir(self.manifest_dir)
+    }
+}
+
+impl fmt::Debug for SolutionEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SolutionEntry")
+            .field("year", &self.year)
+            .field("day", &self.day)
+            .field("part", &self.part)
+            .field("input_file", &self.input_file)
+            .finish()
+    }
+}
+
+inventory::collect!(SolutionEntry);
+
+/// All registered solutions, ordered by year, day and part
+pub fn solutions() -> Vec<&'static SolutionEntry> {
+    let mut entries: Vec<&'static SolutionEntry> =
+        inventory::iter::<SolutionEntry>.into_iter().collect();
+    entries.sort_by_key(|entry| (entry.year, entry.day, entry.part));
+    entries
+}
+
+/// Registered solutions for the selected days, optionally limited to one part
+///
+/// # Param
+/// * `year` - Only select solutions of this year. Every year is selected when `None`
+///
+/// # Return
+/// An error if nothing is registered for the selection
+pub fn select(
+    year: Option<u16>,
+    days: DaySelection,
+    part: Option<Part>,
+) -> AdventResult<Vec<&'static SolutionEntry>> {
+    let selected: Vec<&'static SolutionEntry> = solutions()
+        .into_iter()
+        .filter(|entry| year.is_none_or(|year| entry.year == year))
+        .filter(|entry| days.contains(entry.day))
+        .filter(|entry| part.is_none_or(|part| entry.part == part))
+        .collect();
+
+    if selected.is_empty() {
+        let year_msg = year.map_or_else(String::new, |year| format!(" of {}", year));
+        let part_msg = part.map_or_else(String::new, |part| format!(" part {}", part));
+        return Err(AdventErrors::SolutionNotImplemented(format!(
+            "No solution registered for day {}{}{}",
+            days, part_msg, year_msg
+        )));
+    }
+    Ok(selected)
+}
+
+#[doc(hidden)]
+pub fn parse_erased<S: AdventSolution>(input: &str) -> AdventResult<ParsedInput> {
+    Ok(Box::new(S::parse(input)?))
+}
+
+fn downcast_input<S: AdventSolution>(parsed: &ParsedInput) -> AdventResult<&S::Input> {
+    parsed.downcast_ref::<S::Input>().ok_or_else(|| {
+        AdventErrors::AdventError(format!(
+            "Parsed input is not a {}",
+            std::any::type_name::<S::Input>()
+        ))
+    })
+}
+
+#[doc(hidden)]
+pub fn solve_part1_erased<S: AdventSolution>(parsed: &ParsedInput) -> AdventResult<Answer> {
+    S::part1(downcast_input::<S>(parsed)?)
+}
+
+#[doc(hidden)]
+pub fn solve_part2_erased<S: AdventSolution>(parsed: &ParsedInput) -> AdventResult<Answer> {
+    S::part2(downcast_input::<S>(parsed)?)
+}
+
+/// Registers both parts of a type implementing `AdventSolution`
+///
+/// # Example
+/// ```ignore
+/// register_solution!(Day3, year = 2022, day = 3);
+/// register_solution!(Day2, year = 2022, day = 2, input = "strategy_guide.txt");
+/// ```
+#[macro_export]
+macro_rules! register_solution {
+    ($solution:ty, year = $year:expr, day = $day:expr) => {
+        $crate::register_solution!(
+            $solution,
+            year = $year,
+            day = $day,
+            input = $crate::input::DEFAULT_INPUT_FILE
+        );
+    };
+    ($solution:ty, year = $year:expr, day = $day:expr, input = $input:expr) => {
+        $crate::register_solution!(@part $solution, $year, $day, $input, A, solve_part1_erased);
+        $crate::register_solution!(@part $solution, $year, $day, $input, B, solve_part2_erased);
+    };
+    (@part $solution:ty, $year:expr, $day:expr, $input:expr, $part:ident, $solve:ident) => {
+        $crate::inventory::submit! {
+            $crate::registry::SolutionEntry {
+                year: $year,
+                day: $day,
+                part: $crate::registry::Part::$part,
+                input_file: $input,
+                manifest_dir: env!("CARGO_MANIFEST_DIR"),
+                parse: $crate::registry::parse_erased::<$solution>,
+                solve: $crate::registry::$solve::<$solution>,
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+
+    #[test]
+    fn test_parse_day_selection() {
+        let single = DaySelection::from_str("3").expect("Single day should parse");
+        assert_eq!(single, DaySelection { first: 3, last: 3 });
+
+        let range = DaySelection::from_str("3..5").expect("Range should parse");
+        assert_eq!(range, DaySelection { first: 3, last: 5 });
+        assert!(range.contains(5));
+        assert!(!range.contains(6));
+
+        let inclusive = DaySelection::from_str("3..=5").expect("Inclusive range should parse");
+        assert_eq!(inclusive, range);
+
+        assert!(DaySelection::from_str("5..3").is_err());
+        assert!(DaySelection::from_str("three").is_err());
+    }
+
+    #[test]
+    fn test_parse_part() {
+        assert_eq!(Part::from_str("a").expect("Should parse"), Part::A);
+        assert_eq!(Part::from_str("B").expect("Should parse"), Part::B);
+        assert_eq!(Part::from_str("2").expect("Should parse"), Part::B);
+        assert!(Part::from_str("c").is_err());
+    }
+}
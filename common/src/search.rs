@@ -0,0 +1,325 @@
+//! Path finding over implicit graphs: breadth-first search, Dijkstra and A*.
+//!
+//! A graph only has to list the neighbours of a node, so grids and puzzle states can be
+//! searched without building the whole graph first:
+//!
+//! ```ignore
+//! let graph = FnGraph::new(|point: &Point| {
+//!     grid.neighbours4(*point)
+//!         .filter(|next| grid[*next] != '#')
+//!         .map(|next| (next, 1))
+//!         .collect::<Vec<_>>()
+//! });
+//! let search = bfs(&graph, [start], |point| *point == end);
+//! ```
+
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    marker::PhantomData,
+};
+
+/// Graph whose edges are discovered one node at a time
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    /// Nodes one step away from `node`, with the cost of that step
+    fn neighbours(&self, node: &Self::Node) -> impl IntoIterator<Item = (Self::Node, u64)>;
+}
+
+/// Graph defined by a closure returning the neighbours of a node
+pub struct FnGraph<N, F> {
+    neighbours: F,
+    node: PhantomData<N>,
+}
+
+impl<N, F, I> FnGraph<N, F>
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    pub fn new(neighbours: F) -> Self {
+        Self {
+            neighbours,
+            node: PhantomData,
+        }
+    }
+}
+
+impl<N, F, I> Graph for FnGraph<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    type Node = N;
+
+    fn neighbours(&self, node: &N) -> impl IntoIterator<Item = (N, u64)> {
+        (self.neighbours)(node)
+    }
+}
+
+/// Route through the graph and its total cost
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<N> {
+    /// Nodes from the start to the end, both included
+    pub nodes: Vec<N>,
+    pub cost: u64,
+}
+
+/// Every node reached by a search, with the cheapest known way to reach it
+#[derive(Clone, Debug)]
+pub struct Search<N> {
+    /// Cost of reaching each node, and the node it was reached from
+    reached: HashMap<N, (u64, Option<N>)>,
+    goal: Option<N>,
+}
+
+impl<N: Clone + Eq + Hash> Search<N> {
+    fn new() -> Self {
+        Self {
+            reached: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    /// The first goal found, if any
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    /// Cost of the cheapest path found to `node`
+    pub fn cost_to(&self, node: &N) -> Option<u64> {
+        self.reached.get(node).map(|(cost, _)| *cost)
+    }
+
+    /// Every node the search reached.
+    /// Searches that stop at a goal may not have reached every node of the graph
+    pub fn reached(&self) -> impl Iterator<Item = (&N, u64)> {
+        self.reached.iter().map(|(node, (cost, _))| (node, *cost))
+    }
+
+    /// Path from the closest start to `node`
+    pub fn path_to(&self, node: &N) -> Option<Path<N>> {
+        let (cost, _) = self.reached.get(node)?;
+        let mut nodes = vec![node.clone()];
+        while let Some((_, Some(parent))) = self.reached.get(nodes.last()?) {
+            nodes.push(parent.clone());
+        }
+        nodes.reverse();
+        Some(Path { nodes, cost: *cost })
+    }
+
+    /// Path to the goal
+    pub fn path(&self) -> Option<Path<N>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+}
+
+/// Breadth-first search, treating every step as costing 1
+///
+/// # Param
+/// * `starts` - Nodes the search begins from at a cost of 0
+/// * `is_goal` - Stops the search at the first node it returns true for
+pub fn bfs<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    mut is_goal: impl FnMut(&G::Node) -> bool,
+) -> Search<G::Node> {
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let Entry::Vacant(entry) = search.reached.entry(start.clone()) {
+            entry.insert((0, None));
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, steps)) = queue.pop_front() {
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        for (next, _) in graph.neighbours(&node) {
+            if let Entry::Vacant(entry) = search.reached.entry(next.clone()) {
+                entry.insert((steps + 1, Some(node.clone())));
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    search
+}
+
+/// Cheapest-first search using the step costs of the graph
+///
+/// # Param
+/// * `starts` - Nodes the search begins from at a cost of 0
+/// * `is_goal` - Stops the search at the first node it returns true for
+pub fn dijkstra<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    is_goal: impl FnMut(&G::Node) -> bool,
+) -> Search<G::Node> {
+    astar(graph, starts, is_goal, |_| 0)
+}
+
+/// Dijkstra guided towards the goal by `heuristic`
+///
+/// # Param
+/// * `heuristic` - Estimated cost from a node to the goal.
+///   It must never overestimate, or the path found may not be the cheapest
+pub fn astar<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    mut is_goal: impl FnMut(&G::Node) -> bool,
+    heuristic: impl Fn(&G::Node) -> u64,
+) -> Search<G::Node> {
+    let mut search = Search::new();
+    // Nodes need not be `Ord`, so the queue holds their index in `queued`
+    let mut queued: Vec<(G::Node, u64)> = vec![];
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        if let Entry::Vacant(entry) = search.reached.entry(start.clone()) {
+            entry.insert((0, None));
+            queue.push(Reverse((heuristic(&start), queued.len())));
+            queued.push((start, 0));
+        }
+    }
+
+    while let Some(Reverse((_, idx))) = queue.pop() {
+        let (node, cost) = queued[idx].clone();
+        // Skip entries superseded by a cheaper path found after they were queued
+        if search.cost_to(&node).is_some_and(|best| best < cost) {
+            continue;
+        }
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+
+        for (next, step_cost) in graph.neighbours(&node) {
+            let next_cost = cost + step_cost;
+            let improved = match search.reached.entry(next.clone()) {
+                Entry::Occupied(mut entry) if next_cost < entry.get().0 => {
+                    entry.insert((next_cost, Some(node.clone())));
+                    true
+                }
+                Entry::Occupied(_) => false,
+                Entry::Vacant(entry) => {
+                    entry.insert((next_cost, Some(node.clone())));
+                    true
+                }
+            };
+            if improved {
+                queue.push(Reverse((next_cost + heuristic(&next), queued.len())));
+                queued.push((next, next_cost));
+            }
+        }
+    }
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use crate::grid::{Grid, Point};
+
+    /// a -1- b -1- c
+    ///  \         /
+    ///   ----5----     d is unreachable
+    fn weighted() -> FnGraph<char, impl Fn(&char) -> Vec<(char, u64)>> {
+        FnGraph::new(|node: &char| match node {
+            'a' => vec![('b', 1), ('c', 5)],
+            'b' => vec![('a', 1), ('c', 1)],
+            'c' => vec![('a', 5), ('b', 1)],
+            _ => vec![],
+        })
+    }
+
+    #[test]
+    fn test_bfs_counts_steps() {
+        let search = bfs(&weighted(), ['a'], |node| *node == 'c');
+        let path = search.path().expect("c is reachable");
+        assert_eq!(path.nodes, vec!['a', 'c']);
+        assert_eq!(path.cost, 1);
+        assert_eq!(search.path_to(&'d'), None);
+    }
+
+    #[test]
+    fn test_dijkstra_uses_costs() {
+        let search = dijkstra(&weighted(), ['a'], |node| *node == 'c');
+        let path = search.path().expect("c is reachable");
+        assert_eq!(path.nodes, vec!['a', 'b', 'c']);
+        assert_eq!(path.cost, 2);
+
+        // Without a goal every reachable node is costed
+        let search = dijkstra(&weighted(), ['c'], |_| false);
+        assert_eq!(search.goal(), None);
+        assert_eq!(search.cost_to(&'a'), Some(2));
+        assert_eq!(search.reached().count(), 3);
+    }
+
+    #[test]
+    fn test_multi_source() {
+        let line = FnGraph::new(|node: &i32| {
+            [node - 1, node + 1]
+                .into_iter()
+                .filter(|next| (0..=10).contains(next))
+                .map(|next| (next, 1))
+        });
+        let search = bfs(&line, [0, 10], |_| false);
+        assert_eq!(search.cost_to(&3), Some(3));
+        assert_eq!(search.cost_to(&8), Some(2));
+        assert_eq!(search.path_to(&8).expect("Reachable").nodes, vec![10, 9, 8]);
+    }
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E";
+
+    #[test]
+    fn test_search_grid() {
+        let grid = Grid::parse_chars(MAZE).expect("Maze should parse");
+        let find = |target: char| {
+            grid.iter()
+                .find(|(_, cell)| **cell == target)
+                .map(|(point, _)| point)
+                .expect("Marker in maze")
+        };
+        let (start, end) = (find('S'), find('E'));
+
+        let graph = FnGraph::new(|point: &Point| {
+            grid.neighbours4(*point)
+                .filter(|next| grid[*next] != '#')
+                .map(|next| (next, 1))
+                .collect::<Vec<_>>()
+        });
+
+        let shortest = bfs(&graph, [start], |point| *point == end)
+            .path()
+            .expect("Maze is solvable");
+        assert_eq!(shortest.cost, 15);
+        assert_eq!(shortest.nodes.len(), 16);
+        assert!(shortest
+            .nodes
+            .windows(2)
+            .all(|step| step[0].manhattan_distance(step[1]) == 1));
+
+        let guided = astar(
+            &graph,
+            [start],
+            |point| *point == end,
+            |point| point.manhattan_distance(end),
+        );
+        assert_eq!(guided.path().map(|path| path.cost), Some(15));
+        assert_eq!(
+            dijkstra(&graph, [start], |point| *point == end).cost_to(&end),
+            Some(15)
+        );
+    }
+}
@@ -0,0 +1,21 @@
+//! Benchmarks day 1 over the committed input
+use advent_common::command::AdventSolution;
+use advent_day1::solution::Day1;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const INPUT: &str = include_str!("../input.txt");
+
+fn bench_day1(c: &mut Criterion) {
+    c.bench_function("day1/parse", |b| b.iter(|| Day1::parse(black_box(INPUT))));
+
+    let parsed = Day1::parse(INPUT).expect("Committed input should parse");
+    c.bench_function("day1/part_a", |b| {
+        b.iter(|| Day1::part1(black_box(&parsed)))
+    });
+    c.bench_function("day1/part_b", |b| {
+        b.iter(|| Day1::part2(black_box(&parsed)))
+    });
+}
+
+criterion_group!(benches, bench_day1);
+criterion_main!(benches);
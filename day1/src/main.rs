@@ -1,14 +1,48 @@
 //! Main file to produce local binary
+//! Usage: `day1 [--input <path>]`, where a path of `-` reads the input from stdin
 mod solution;
 
+use advent_common::{
+    command::{AdventSolution, Answer},
+    config::{AdventConfig, Verbosity},
+    errors::{AdventErrors, AdventResult},
+    input::{InputLocator, InputSource},
+    logging,
+    tracing::error,
+};
+
+/// The `--input` (or `-i`) argument, if given
+fn input_arg() -> AdventResult<Option<InputSource>> {
+    let mut args = std::env::args().skip(1);
+    match (args.next().as_deref(), args.next(), args.next()) {
+        (None, ..) => Ok(None),
+        (Some("-i" | "--input"), Some(path), None) => Ok(Some(path.parse()?)),
+        _ => Err(AdventErrors::AdventError(
+            "Usage: day1 [--input <path>]".to_string(),
+        )),
+    }
+}
+
+fn solve() -> AdventResult<Answer> {
+    let source = InputLocator::new(1, "input.txt")
+        .explicit(input_arg()?)
+        .config_dir(AdventConfig::load()?.input_dir)
+        .manifest_dir(env!("CARGO_MANIFEST_DIR"))
+        .locate()?;
+    let input = source.read_to_string()?;
+    let parsed = solution::Day1::parse(&input).map_err(|err| err.in_file(source.label()))?;
+    solution::Day1::part1(&parsed)
+}
+
 fn main() {
-    let res = solution::solve_problem_1a();
+    logging::init(Verbosity::default());
+    let res = solve();
     match res {
         Err(err) => {
-            println!("Error solving the problem: {:?}", err)
+            error!("Error solving the problem: {:?}", err)
         }
         Ok(res) => {
-            println!("{}", res);
+            println!("{}", res.value);
         }
     };
 }
@@ -1,97 +1,84 @@
-use advent_common::{command::AdventSolution, errors::AdventResult, utils};
-use clap::Args;
-use std::{cmp::Reverse, fs, path::PathBuf};
-
-/// Finds the elf with the highest calories and returns the amount
-///
-/// # Return
-/// The calories of the elf that has the most
-///
-/// # Param
-/// The read in string from the input file
-fn find_highest_cal(input: String) -> AdventResult<u64> {
-    let mut highest_cal: u64 = 0;
-    let mut cur_elf_cal: u64 = 0;
-    for line in input.lines() {
-        if line.is_empty() {
-            if cur_elf_cal > highest_cal {
-                highest_cal = cur_elf_cal;
-            }
-            cur_elf_cal = 0;
-        } else {
-            cur_elf_cal += line.parse::<u64>()?;
-        }
-    }
-
-    Ok(highest_cal)
-}
+use advent_common::{
+    command::{AdventSolution, Answer},
+    errors::AdventResult,
+    parse::groups,
+    register_solution,
+};
+use std::cmp::Reverse;
 
 ///
 /// # Return
 /// A sorted vector
-fn generate_cal_list(input: String) -> AdventResult<Vec<u64>> {
+pub fn generate_cal_list(input: &str) -> AdventResult<Vec<u64>> {
     let mut res: Vec<u64> = Vec::new();
-
-    let mut cur_elf_cal = 0;
-
-    for line in input.lines() {
-        if line.is_empty() {
-            res.push(cur_elf_cal);
-            cur_elf_cal = 0;
-        } else {
+    for elf in groups(input) {
+        let mut cur_elf_cal = 0;
+        for line in elf {
             cur_elf_cal += line.parse::<u64>()?;
         }
+        res.push(cur_elf_cal);
     }
 
     res.sort_by_key(|w| Reverse(*w));
     Ok(res)
 }
 
-// Obtains the solution to the problem
-pub(crate) fn solve_problem_1a() -> AdventResult<String> {
-    let project_root_dir: PathBuf = utils::get_project_root()?;
-    let input_file_path: PathBuf = project_root_dir.as_path().join("day1").join("input.txt");
+/// Run the program for Day 1
+#[derive(Clone, Debug, Default)]
+pub struct Day1 {}
 
-    let input: String = fs::read_to_string(input_file_path)?;
-    let res: u64 = find_highest_cal(input)?;
-    let res_msg = format!("Elf with highest amount of calories has: {}", res);
-    Ok(res_msg)
-}
-
-pub(crate) fn solve_problem_1b() -> AdventResult<String> {
-    let project_root_dir: PathBuf = utils::get_project_root()?;
-    let input_file_path: PathBuf = project_root_dir.as_path().join("day1").join("input.txt");
-
-    let input: String = fs::read_to_string(input_file_path)?;
+impl AdventSolution for Day1 {
+    /// Calories carried by each elf, highest first
+    type Input = Vec<u64>;
 
-    let mut cal_list: Vec<u64> = generate_cal_list(input)?;
-
-    while cal_list.len() < 3 {
-        cal_list.push(0);
+    fn parse(input: &str) -> AdventResult<Vec<u64>> {
+        generate_cal_list(input)
     }
 
-    let combined_calories = cal_list[0] + cal_list[1] + cal_list[2];
+    fn part1(cal_list: &Vec<u64>) -> AdventResult<Answer> {
+        let res: u64 = cal_list.first().copied().unwrap_or(0);
+        Ok(Answer::integer(res).with_description("Calories carried by the elf with the most"))
+    }
 
-    let res_msg = format!("Total calories for top 3 elves: {}", combined_calories);
-    Ok(res_msg)
+    fn part2(cal_list: &Vec<u64>) -> AdventResult<Answer> {
+        let combined_calories: u64 = cal_list.iter().take(3).sum();
+        Ok(Answer::integer(combined_calories).with_description("Total calories for top 3 elves"))
+    }
 }
 
-/// Run the program for Day 1a
-#[derive(Args, Clone, Debug)]
-pub struct Day1a {}
+register_solution!(Day1, year = 2022, day = 1);
 
-impl AdventSolution for Day1a {
-    fn find_solution(&self) -> AdventResult<String> {
-        solve_problem_1a()
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use advent_common::{command::AnswerValue, errors::AdventErrors, fixtures::assert_examples};
+
+    #[test]
+    fn test_examples() {
+        assert_examples(1, env!("CARGO_MANIFEST_DIR"));
     }
-}
 
-/// Run the program for Day 1b
-#[derive(Args, Clone, Debug)]
-pub struct Day1b {}
+    #[test]
+    fn test_last_elf_counted() {
+        let input = "1000\n\n2000\n3000";
+        let cal_list = generate_cal_list(input).expect("Should parse");
+        assert_eq!(cal_list, vec![5000, 1000]);
+        assert_eq!(
+            Day1::part1(&cal_list).expect("Should solve").value,
+            AnswerValue::Integer(5000)
+        );
+    }
 
-impl AdventSolution for Day1b {
-    fn find_solution(&self) -> AdventResult<String> {
-        solve_problem_1b()
+    #[test]
+    fn test_bad_calories_located() {
+        let err = generate_cal_list("1000\n\n20x0").expect_err("Should not parse");
+        match err {
+            AdventErrors::InvalidInput { location, .. } => {
+                assert_eq!((location.line, location.column), (3, 1));
+                assert_eq!(location.text, "20x0");
+            }
+            err => panic!("Unexpected error {:?}", err),
+        }
     }
 }
@@ -0,0 +1,21 @@
+//! Benchmarks day 2 over the committed input
+use advent_common::command::AdventSolution;
+use advent_day2::solution::Day2;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const INPUT: &str = include_str!("../strategy_guide.txt");
+
+fn bench_day2(c: &mut Criterion) {
+    c.bench_function("day2/parse", |b| b.iter(|| Day2::parse(black_box(INPUT))));
+
+    let parsed = Day2::parse(INPUT).expect("Committed input should parse");
+    c.bench_function("day2/part_a", |b| {
+        b.iter(|| Day2::part1(black_box(&parsed)))
+    });
+    c.bench_function("day2/part_b", |b| {
+        b.iter(|| Day2::part2(black_box(&parsed)))
+    });
+}
+
+criterion_group!(benches, bench_day2);
+criterion_main!(benches);
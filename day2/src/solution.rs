@@ -1,10 +1,11 @@
 use advent_common::{
-    command::AdventSolution,
+    command::{AdventSolution, Answer},
     errors::{AdventErrors, AdventResult},
-    utils,
+    parse::{parse_lines, Span},
+    register_solution,
+    tracing::trace,
 };
-use clap::Args;
-use std::{fs, path::PathBuf, str::FromStr};
+use std::str::FromStr;
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 enum GameOptions {
@@ -158,93 +159,70 @@ impl FromStr for PlayerStrategyMap {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 enum MatchResult {
     Opponent = 0,
     Tie = 3,
     Player = 6,
 }
 
-fn validate_line_len(raw_line: &str, split_line: &Vec<&str>) -> AdventResult<()> {
-    let len = split_line.len();
-    match len {
-        len if len > 2 => Err(AdventErrors::AdventError(format!(
-            "Line {} had more than 2 entries",
-            raw_line
-        ))),
-        len if len < 2 => Err(AdventErrors::AdventError(format!(
-            "Line {} had less than 2 entries",
-            raw_line
-        ))),
-        _ => Ok(()),
-    }
-}
-
 fn get_match_score(player_choice: &GameOptions, match_winner: &MatchResult) -> AdventResult<u8> {
     let match_res_score = *match_winner as u8;
     let shape_selection_score = *player_choice as u8;
     let current_match_res = match_res_score + shape_selection_score;
-    println!("\n---------------------------");
-    println!("Current match info:");
-    println!(
-        "Player: {:?} vs Opponent: {:?}",
-        player_choice, match_winner
+    trace!(
+        player = ?player_choice,
+        winner = ?match_winner,
+        result_points = match_res_score,
+        shape_points = shape_selection_score,
+        "Scored match with {} points",
+        current_match_res
     );
-    println!(
-        "match_winner = {:?} - {} points",
-        match_winner, match_res_score
-    );
-    println!("score from shape = {:?}", shape_selection_score);
-    println!("Overall points from match: {:?}", current_match_res);
-    println!("---------------------------\n");
 
     Ok(current_match_res)
 }
 
-/// Run the program for Day 2a
-#[derive(Args, Clone, Debug)]
-pub struct Day2a {
-    /// Path to strategy guide file relative to this file's directory
-    #[arg(short, long, default_value = "strategy_guide.txt")]
-    file_name: PathBuf,
+/// A single line of the strategy guide, still encrypted
+#[derive(Copy, Clone, Debug)]
+pub struct Round {
+    opponent: OpponentStrategyMap,
+    player: PlayerStrategyMap,
 }
 
-impl AdventSolution for Day2a {
-    fn find_solution(&self) -> AdventResult<String> {
-        self.solve_problem_2a()
+impl Round {
+    /// Parses a line of the strategy guide, i.e. `A Y`
+    fn from_line(line: Span) -> AdventResult<Round> {
+        let [opponent, player] = line.split_fixed::<2>(" ").map_err(|err| {
+            err.with_hint(
+                "Each line holds the opponent's choice and the player's column, i.e. `A Y`",
+            )
+        })?;
+
+        Ok(Round {
+            opponent: OpponentStrategyMap::from_str(opponent.as_str())
+                .map_err(|_| opponent.error("Expected the opponent's choice: A, B or C"))?,
+            player: PlayerStrategyMap::from_str(player.as_str())
+                .map_err(|_| player.error("Expected the player's column: X, Y or Z"))?,
+        })
     }
 }
 
-/// Run the program for Day 2b
-#[derive(Args, Clone, Debug)]
-pub struct Day2b {
-    /// Path to strategy guide file relative to this file's directory
-    #[arg(short, long, default_value = "strategy_guide.txt")]
-    file_name: PathBuf,
-}
+/// Run the program for Day 2
+#[derive(Clone, Debug, Default)]
+pub struct Day2 {}
 
-impl AdventSolution for Day2b {
-    fn find_solution(&self) -> AdventResult<String> {
-        self.solve_problem_2b()
-    }
-}
-
-impl Day2a {
-    pub(crate) fn solve_problem_2a(&self) -> AdventResult<String> {
-        let project_root_dir: PathBuf = utils::get_project_root()?;
-        let input_file_path: PathBuf = project_root_dir
-            .as_path()
-            .join("day2")
-            .join(&self.file_name);
+impl AdventSolution for Day2 {
+    type Input = Vec<Round>;
 
-        let input: String = fs::read_to_string(input_file_path)?;
+    fn parse(input: &str) -> AdventResult<Vec<Round>> {
+        parse_lines(input, Round::from_line)
+    }
 
+    fn part1(rounds: &Vec<Round>) -> AdventResult<Answer> {
         let mut total_score: u64 = 0;
-        for line in input.lines() {
-            let split_line = line.split(' ').collect::<Vec<&str>>();
-            validate_line_len(line, &split_line)?;
-            let opponent_input = OpponentStrategyMap::from_str(split_line[0])?.de_encrypt_input();
-            let player_input = PlayerStrategyMap::from_str(split_line[1])?.de_encrypt_input();
+        for round in rounds {
+            let opponent_input = round.opponent.de_encrypt_input();
+            let player_input = round.player.de_encrypt_input();
             let match_winner = GameOptions::resolve_match(&player_input, &opponent_input)?;
 
             let current_match_res = get_match_score(&player_input, &match_winner)?;
@@ -252,27 +230,14 @@ impl Day2a {
             total_score += current_match_res as u64;
         }
 
-        let res_str = format!("The total score is {}", total_score);
-        Ok(res_str)
+        Ok(Answer::integer(total_score).with_description("Total score"))
     }
-}
-
-impl Day2b {
-    fn solve_problem_2b(&self) -> AdventResult<String> {
-        let project_root_dir: PathBuf = utils::get_project_root()?;
-        let input_file_path: PathBuf = project_root_dir
-            .as_path()
-            .join("day2")
-            .join(&self.file_name);
 
-        let input: String = fs::read_to_string(input_file_path)?;
+    fn part2(rounds: &Vec<Round>) -> AdventResult<Answer> {
         let mut total_score: u64 = 0;
-        for line in input.lines() {
-            let split_line = line.split(' ').collect::<Vec<&str>>();
-            validate_line_len(line, &split_line)?;
-            let opponent_input = OpponentStrategyMap::from_str(split_line[0])?.de_encrypt_input();
-            let desired_match_result =
-                PlayerStrategyMap::from_str(split_line[1])?.translate_to_match_result();
+        for round in rounds {
+            let opponent_input = round.opponent.de_encrypt_input();
+            let desired_match_result = round.player.translate_to_match_result();
 
             let player_choice =
                 GameOptions::determine_players_choice(&opponent_input, &desired_match_result)?;
@@ -284,15 +249,41 @@ impl Day2b {
             total_score += current_match_res as u64;
         }
 
-        let res_str = format!("The total score is {}", total_score);
-        Ok(res_str)
+        Ok(Answer::integer(total_score).with_description("Total score"))
     }
 }
 
+register_solution!(Day2, year = 2022, day = 2, input = "strategy_guide.txt");
+
 #[cfg(test)]
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use advent_common::fixtures::assert_examples;
+
+    #[test]
+    fn test_examples() {
+        assert_examples(2, env!("CARGO_MANIFEST_DIR"));
+    }
+
+    #[test]
+    fn test_bad_round_located() {
+        match Day2::parse("A Y\nB Q") {
+            Err(AdventErrors::InvalidInput { location, .. }) => {
+                assert_eq!((location.line, location.column), (2, 3));
+                assert_eq!(location.text, "Q");
+            }
+            res => panic!("Unexpected result {:?}", res),
+        }
+
+        match Day2::parse("A Y Z") {
+            Err(AdventErrors::InvalidInput { location, .. }) => {
+                assert_eq!((location.line, location.column), (1, 1));
+                assert_eq!(location.text, "A Y Z");
+            }
+            res => panic!("Unexpected result {:?}", res),
+        }
+    }
 
     #[test]
     fn test_resolve_match() {
@@ -0,0 +1,21 @@
+//! Benchmarks day 3 over the committed input
+use advent_common::command::AdventSolution;
+use advent_day3::solution::Day3;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const INPUT: &str = include_str!("../input.txt");
+
+fn bench_day3(c: &mut Criterion) {
+    c.bench_function("day3/parse", |b| b.iter(|| Day3::parse(black_box(INPUT))));
+
+    let parsed = Day3::parse(INPUT).expect("Committed input should parse");
+    c.bench_function("day3/part_a", |b| {
+        b.iter(|| Day3::part1(black_box(&parsed)))
+    });
+    c.bench_function("day3/part_b", |b| {
+        b.iter(|| Day3::part2(black_box(&parsed)))
+    });
+}
+
+criterion_group!(benches, bench_day3);
+criterion_main!(benches);
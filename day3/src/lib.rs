@@ -1,2 +1 @@
-mod encoding;
 pub mod solution;
@@ -1,140 +1,106 @@
 //! Implements the solution to day3
 
-use crate::encoding::ItemPriority;
 use advent_common::{
-    command::AdventSolution,
+    alphabet::{Alphabet, AlphabetSet},
+    command::{AdventSolution, Answer},
     errors::{AdventErrors, AdventResult},
-    utils,
+    parse::{parse_lines, Span},
+    register_solution,
 };
 
-use clap::Args;
-use std::{fs, path::PathBuf, str::FromStr};
+/// Every possible item, ordered so an item's priority is its position
+const ITEMS: Alphabet = Alphabet::LETTERS;
 
-#[derive(Clone, Debug, Default)]
-struct Rucksack {
-    compartment_one: String,
-    compartment_two: String,
+#[derive(Clone, Debug)]
+pub struct Rucksack {
+    items_one: AlphabetSet,
+    items_two: AlphabetSet,
 }
 
-impl Rucksack {
-    fn find_duplicate(&self) -> AdventResult<String> {
-        for item in self.compartment_one.chars() {
-            if self.compartment_two.contains(item) {
-                return Ok(item.to_string());
-            }
+impl Default for Rucksack {
+    fn default() -> Self {
+        Self {
+            items_one: AlphabetSet::new(ITEMS),
+            items_two: AlphabetSet::new(ITEMS),
         }
-        Ok("".to_string())
-    }
-    pub(crate) fn merge_compartments(&self) -> AdventResult<String> {
-        let self_combined_sack = format!("{}{}", self.compartment_one, self.compartment_two);
-        Ok(self_combined_sack)
     }
+}
 
-    pub(crate) fn find_shared_items(&self, sack2: &Rucksack) -> AdventResult<Vec<char>> {
-        let self_combined_sack = self.merge_compartments()?;
-        let other_combined_sack = sack2.merge_compartments()?;
+/// The one item of `items`, or an error if there is not exactly one
+///
+/// # Param
+/// * `holders` - Who shares the items, for the error message
+fn only_item(items: AlphabetSet, holders: &str) -> AdventResult<char> {
+    items.single().ok_or_else(|| {
+        AdventErrors::AdventError(match items.len() {
+            0 => format!("Could not find item common between {}", holders),
+            _ => format!(
+                "Ambiguous common item: `{}` are all common between {}",
+                items, holders
+            ),
+        })
+    })
+}
 
-        let mut common_items = vec![];
+impl Rucksack {
+    /// The item in both compartments
+    fn find_duplicate(&self) -> AdventResult<char> {
+        only_item(self.items_one & self.items_two, "both compartments")
+    }
 
-        for item in self_combined_sack.chars() {
-            if other_combined_sack.contains(item) {
-                common_items.push(item);
-            }
-        }
-        if common_items.is_empty() {
-            Err(AdventErrors::AdventError(
-                "Could not find shared item".to_string(),
-            ))
-        } else {
-            Ok(common_items)
-        }
+    /// Every item in either compartment
+    pub(crate) fn items(&self) -> AlphabetSet {
+        self.items_one | self.items_two
     }
 }
 
-struct Day3Common {}
+pub struct Day3Common {}
 
 impl Day3Common {
-    fn generate_rucksacks(input: String) -> AdventResult<Vec<Rucksack>> {
-        let mut rucksacks: Vec<Rucksack> = Vec::new();
-        for line in input.lines() {
-            let rucksack = Self::parse_for_rustsack(line)?;
-            rucksacks.push(rucksack)
-        }
-
-        Ok(rucksacks)
+    pub fn generate_rucksacks(input: &str) -> AdventResult<Vec<Rucksack>> {
+        parse_lines(input, Self::parse_for_rustsack)
     }
 
-    fn parse_for_rustsack(line: &str) -> AdventResult<Rucksack> {
+    fn parse_for_rustsack(line: Span) -> AdventResult<Rucksack> {
+        // Items are single bytes, so the line can be halved by its length
+        if let Some((_, item_span)) = line.char_spans().find(|(item, _)| !item.is_ascii()) {
+            return Err(item_span.error("Items are the letters a-z and A-Z"));
+        }
         let num_items = line.len();
         if num_items % 2 == 1 {
-            let err_msg = format!("Line has odd number of items {}, line: {}", num_items, line);
-            println!("{}", err_msg);
-            return Err(AdventErrors::AdventError(err_msg));
+            return Err(line
+                .error(format!("Line has odd number of items {}", num_items))
+                .with_hint("Both compartments of a rucksack hold the same number of items"));
         }
 
         let middle_idx = num_items >> 1;
-        let (compartment_one, compartment_two) = line.split_at(middle_idx);
+        let (compartment_one, compartment_two) = line.split_at(middle_idx)?;
         let rucksack = Rucksack {
-            compartment_one: compartment_one.to_string(),
-            compartment_two: compartment_two.to_string(),
+            items_one: Self::parse_items(compartment_one)?,
+            items_two: Self::parse_items(compartment_two)?,
         };
 
         Ok(rucksack)
     }
-}
-
-/// Run the program for Day 3a
-#[derive(Args, Clone, Debug)]
-pub struct Day3a {
-    /// Path to strategy guide file relative to this file's directory
-    #[arg(short, long, default_value = "input.txt")]
-    file_name: PathBuf,
-}
-#[derive(Debug)]
 
-struct ElfGroup {
-    group: [Rucksack; 3],
-}
-
-/// Run the program for Day 3b
-#[derive(Args, Clone, Debug)]
-pub struct Day3b {
-    /// Path to strategy guide file relative to this file's directory
-    #[arg(short, long, default_value = "input.txt")]
-    file_name: PathBuf,
-}
-
-impl Day3a {
-    pub(crate) fn solve_problem_3a(&self) -> AdventResult<String> {
-        let project_root_dir: PathBuf = utils::get_project_root()?;
-        let input_file_path: PathBuf = project_root_dir
-            .as_path()
-            .join("day3")
-            .join(&self.file_name);
-
-        let input: String = fs::read_to_string(input_file_path)?;
-
-        let mut total_priority: u64 = 0;
-        let rucksacks = Day3Common::generate_rucksacks(input)?;
-
-        for rucksack in rucksacks {
-            total_priority += Self::get_duplicate_priority(rucksack)? as u64;
+    fn parse_items(compartment: Span) -> AdventResult<AlphabetSet> {
+        let mut items = AlphabetSet::new(ITEMS);
+        for (item, item_span) in compartment.char_spans() {
+            items
+                .insert(item)
+                .map_err(|_| item_span.error("Items are the letters a-z and A-Z"))?;
         }
-
-        Ok(format!("Total Priority: {}", total_priority))
-    }
-
-    fn get_duplicate_priority(rucksack: Rucksack) -> AdventResult<u8> {
-        let duplicate_item = rucksack.find_duplicate()?;
-        let item_priority = ItemPriority::from_str(&duplicate_item)? as u8;
-        Ok(item_priority)
+        Ok(items)
     }
 }
 
-impl AdventSolution for Day3a {
-    fn find_solution(&self) -> AdventResult<String> {
-        self.solve_problem_3a()
-    }
+/// Run the program for Day 3
+#[derive(Clone, Debug, Default)]
+pub struct Day3 {}
+
+#[derive(Debug)]
+struct ElfGroup {
+    group: [Rucksack; 3],
 }
 
 impl ElfGroup {
@@ -152,130 +118,203 @@ impl ElfGroup {
 
     /// Finds the item common to all 3 elves in the group
     pub(crate) fn find_common_item(&self) -> AdventResult<char> {
-        let common_a_b = self.group[0].find_shared_items(&self.group[1])?;
-        let common_b_c = self.group[1].find_shared_items(&self.group[2])?;
+        let common_items = AlphabetSet::intersect_all(self.group.iter().map(Rucksack::items))
+            .unwrap_or_else(|| AlphabetSet::new(ITEMS));
+        only_item(common_items, "all 3 elves")
+    }
+}
 
-        for common_item in common_a_b.iter() {
-            if common_b_c.contains(common_item) {
-                return Ok(common_item.to_owned());
-            }
+impl Day3 {
+    fn get_duplicate_priority(rucksack: &Rucksack) -> AdventResult<u8> {
+        let duplicate_item = rucksack.find_duplicate()?;
+        let item_priority = ITEMS.priority(duplicate_item).ok_or_else(|| {
+            AdventErrors::AdventError(format!("{} is not an item", duplicate_item))
+        })?;
+        Ok(item_priority as u8)
+    }
+
+    /// Divide all elf rucksacks into groups
+    fn create_rucksack_groups(rucksacks: &[Rucksack]) -> AdventResult<Vec<ElfGroup>> {
+        let mut groups: Vec<ElfGroup> = vec![];
+        for sack_array in rucksacks.rchunks(3) {
+            let grouping = ElfGroup::from_vec(sack_array)?;
+            groups.push(grouping);
         }
 
-        Err(AdventErrors::AdventError(
-            "Could not find item common between all 3 elves".to_string(),
-        ))
+        Ok(groups)
     }
 }
 
-impl Day3b {
-    fn solve_problem_3b(&self) -> AdventResult<String> {
-        let project_root_dir: PathBuf = utils::get_project_root()?;
-        let input_file_path: PathBuf = project_root_dir
-            .as_path()
-            .join("day3")
-            .join(&self.file_name);
+impl AdventSolution for Day3 {
+    type Input = Vec<Rucksack>;
 
-        let input: String = fs::read_to_string(input_file_path)?;
+    fn parse(input: &str) -> AdventResult<Vec<Rucksack>> {
+        Day3Common::generate_rucksacks(input)
+    }
 
+    fn part1(rucksacks: &Vec<Rucksack>) -> AdventResult<Answer> {
         let mut total_priority: u64 = 0;
-        let rucksacks = Day3Common::generate_rucksacks(input)?;
+        for rucksack in rucksacks {
+            total_priority += Self::get_duplicate_priority(rucksack)? as u64;
+        }
 
+        Ok(Answer::integer(total_priority).with_description("Total Priority"))
+    }
+
+    fn part2(rucksacks: &Vec<Rucksack>) -> AdventResult<Answer> {
+        let mut total_priority: u64 = 0;
         let groups = Self::create_rucksack_groups(rucksacks)?;
         for group in groups.iter() {
             let common_item = group.find_common_item()?;
-            let item_priority: ItemPriority = ItemPriority::from_str(&common_item.to_string())?;
+            let item_priority = ITEMS.priority(common_item).ok_or_else(|| {
+                AdventErrors::AdventError(format!("{} is not an item", common_item))
+            })?;
             total_priority += item_priority as u64;
         }
 
-        Ok(format!("Total Priority: {}", total_priority))
-    }
-
-    /// Divide all elf rucksacks into groups
-    fn create_rucksack_groups(rucksacks: Vec<Rucksack>) -> AdventResult<Vec<ElfGroup>> {
-        let mut groups: Vec<ElfGroup> = vec![];
-        for sack_array in rucksacks.rchunks(3) {
-            let grouping = ElfGroup::from_vec(sack_array)?;
-            groups.push(grouping);
-        }
-
-        Ok(groups)
+        Ok(Answer::integer(total_priority).with_description("Total Priority"))
     }
 }
 
-impl AdventSolution for Day3b {
-    fn find_solution(&self) -> AdventResult<String> {
-        self.solve_problem_3b()
-    }
-}
+register_solution!(Day3, year = 2022, day = 3);
 
 #[cfg(test)]
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use advent_common::fixtures::assert_examples;
 
     #[test]
-    fn test_get_duplicate_priority() {
-        let rucksack = Rucksack {
-            compartment_one: "vJrwpWtwJgWr".to_string(),
-            compartment_two: "hcsFMMfFFhFp".to_string(),
-        };
-        let item_priority = Day3a::get_duplicate_priority(rucksack).expect("Should not error");
-        assert!(item_priority == ItemPriority::LOWER_P as u8);
-
-        let rucksack = Rucksack {
-            compartment_one: "jqHRNqRjqzjGDLGL".to_string(),
-            compartment_two: "rsFMfFZSrLrFZsSL".to_string(),
-        };
-        let item_priority = Day3a::get_duplicate_priority(rucksack).expect("Should not error");
-        assert!(item_priority == ItemPriority::L as u8);
+    fn test_examples() {
+        assert_examples(3, env!("CARGO_MANIFEST_DIR"));
+    }
 
-        let rucksack = Rucksack {
-            compartment_one: "PmmdzqPrV".to_string(),
-            compartment_two: "vPwwTWBwg".to_string(),
-        };
-        let item_priority = Day3a::get_duplicate_priority(rucksack).expect("Should not error");
-        assert!(item_priority == ItemPriority::P as u8);
+    #[test]
+    fn test_get_duplicate_priority() {
+        let rucksack = Day3Common::parse_for_rustsack(Span::new(1, "vJrwpWtwJgWrhcsFMMfFFhFp"))
+            .expect("Rustsack should be parsable");
+        let item_priority = Day3::get_duplicate_priority(&rucksack).expect("Should not error");
+        assert!(item_priority == 16);
+
+        let rucksack =
+            Day3Common::parse_for_rustsack(Span::new(1, "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL"))
+                .expect("Rustsack should be parsable");
+        let item_priority = Day3::get_duplicate_priority(&rucksack).expect("Should not error");
+        assert!(item_priority == 38);
+
+        let rucksack = Day3Common::parse_for_rustsack(Span::new(1, "PmmdzqPrVvPwwTWBwg"))
+            .expect("Rustsack should be parsable");
+        let item_priority = Day3::get_duplicate_priority(&rucksack).expect("Should not error");
+        assert!(item_priority == 42);
+
+        let rucksack = Day3Common::parse_for_rustsack(Span::new(1, "abcd"))
+            .expect("Rustsack should be parsable");
+        assert!(Day3::get_duplicate_priority(&rucksack).is_err());
+        let rucksack = Day3Common::parse_for_rustsack(Span::new(1, "abab"))
+            .expect("Rustsack should be parsable");
+        assert!(Day3::get_duplicate_priority(&rucksack).is_err());
     }
 
     #[test]
     fn test_parse_for_rustsack() {
         let line = "vJrwpWtwJgWrhcsFMMfFFhFp";
-        let rustsack = Day3Common::parse_for_rustsack(line).expect("Rustsack should be parsable");
+        let rustsack = Day3Common::parse_for_rustsack(Span::new(1, line))
+            .expect("Rustsack should be parsable");
+        let expected_one = AlphabetSet::from_text(ITEMS, "vJrwpWtwJgWr").expect("Letters");
+        let expected_two = AlphabetSet::from_text(ITEMS, "hcsFMMfFFhFp").expect("Letters");
         assert!(
-            rustsack.compartment_one == "vJrwpWtwJgWr",
-            "Compartment one: {}. Expected: vJrwpWtwJgWr",
-            rustsack.compartment_one
+            rustsack.items_one == expected_one,
+            "Compartment one: {}. Expected: {}",
+            rustsack.items_one,
+            expected_one
         );
         assert!(
-            rustsack.compartment_two == "hcsFMMfFFhFp",
-            "Compartment two: {}. Expected: hcsFMMfFFhFp",
-            rustsack.compartment_two
+            rustsack.items_two == expected_two,
+            "Compartment two: {}. Expected: {}",
+            rustsack.items_two,
+            expected_two
         );
     }
 
     #[test]
-    fn test_merge_compartments() {
+    fn test_unknown_item_located() {
+        match Day3Common::generate_rucksacks("ab1d") {
+            Err(AdventErrors::InvalidInput { location, .. }) => {
+                assert_eq!((location.line, location.column), (1, 3));
+                assert_eq!(location.text, "1");
+            }
+            res => panic!("Unexpected result {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_non_ascii_item_located() {
+        match Day3Common::generate_rucksacks("ab\naéb") {
+            Err(AdventErrors::InvalidInput { location, .. }) => {
+                assert_eq!((location.line, location.column), (2, 2));
+                assert_eq!(location.text, "é");
+            }
+            res => panic!("Unexpected result {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_odd_rucksack_located() {
+        match Day3Common::generate_rucksacks("vJrwpWtwJgWrhcsFMMfFFhFp\nabc") {
+            Err(AdventErrors::InvalidInput { location, .. }) => {
+                assert_eq!((location.line, location.column), (2, 1));
+                assert_eq!(location.text, "abc");
+            }
+            res => panic!("Unexpected result {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_rucksack_items() {
         let line = "vJrwpWtwJgWrhcsFMMfFFhFp";
-        let rustsack = Day3Common::parse_for_rustsack(line).expect("Rustsack should be parsable");
+        let rustsack = Day3Common::parse_for_rustsack(Span::new(1, line))
+            .expect("Rustsack should be parsable");
 
-        assert!(
-            rustsack
-                .merge_compartments()
-                .expect("Merging shouldn't fail")
-                == line
-        );
+        let items = rustsack.items();
+        assert_eq!(items.len(), 14);
+        assert!(line.chars().all(|item| items.contains(item)));
+    }
+
+    #[test]
+    fn test_ambiguous_common_item() {
+        // The same rucksack twice shares `r`, `s`, `F`, `M` and `f` with the first
+        let line1 = "vJrwpWtwJgWrhcsFMMfFFhFp";
+        let line2 = "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL";
+        let rustsacks: Vec<Rucksack> = [line1, line2, line2]
+            .iter()
+            .map(|line| {
+                Day3Common::parse_for_rustsack(Span::new(1, line))
+                    .expect("Rustsack should be parsable")
+            })
+            .collect();
+
+        let group = ElfGroup::from_vec(&rustsacks).expect("Creating group should not fail");
+        match group.find_common_item() {
+            Err(AdventErrors::AdventError(msg)) => {
+                assert!(msg.contains("Ambiguous common item"), "{}", msg)
+            }
+            res => panic!("Expected an ambiguous common item, got {:?}", res),
+        }
     }
 
     #[test]
     fn test_find_shared_items() {
         let line1 = "vJrwpWtwJgWrhcsFMMfFFhFp";
-        let rustsack1 = Day3Common::parse_for_rustsack(line1).expect("Rustsack should be parsable");
+        let rustsack1 = Day3Common::parse_for_rustsack(Span::new(1, line1))
+            .expect("Rustsack should be parsable");
 
         let line2 = "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL";
-        let rustsack2 = Day3Common::parse_for_rustsack(line2).expect("Rustsack should be parsable");
+        let rustsack2 = Day3Common::parse_for_rustsack(Span::new(1, line2))
+            .expect("Rustsack should be parsable");
 
-        let line3 = "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL";
-        let rustsack3 = Day3Common::parse_for_rustsack(line3).expect("Rustsack should be parsable");
+        let line3 = "PmmdzqPrVvPwwTWBwg";
+        let rustsack3 = Day3Common::parse_for_rustsack(Span::new(1, line3))
+            .expect("Rustsack should be parsable");
 
         let group = ElfGroup::from_vec(&[rustsack1, rustsack2, rustsack3])
             .expect("Creating group should not fail");
@@ -285,13 +324,16 @@ mod tests {
         assert!(common_item == 'r');
 
         let line4 = "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn";
-        let rustsack4 = Day3Common::parse_for_rustsack(line4).expect("Rustsack should be parsable");
+        let rustsack4 = Day3Common::parse_for_rustsack(Span::new(1, line4))
+            .expect("Rustsack should be parsable");
 
         let line5 = "ttgJtRGJQctTZtZT";
-        let rustsack5 = Day3Common::parse_for_rustsack(line5).expect("Rustsack should be parsable");
+        let rustsack5 = Day3Common::parse_for_rustsack(Span::new(1, line5))
+            .expect("Rustsack should be parsable");
 
         let line6 = "CrZsJsPPZsGzwwsLwLmpwMDw";
-        let rustsack6 = Day3Common::parse_for_rustsack(line6).expect("Rustsack should be parsable");
+        let rustsack6 = Day3Common::parse_for_rustsack(Span::new(1, line6))
+            .expect("Rustsack should be parsable");
 
         let group = ElfGroup::from_vec(&[rustsack4, rustsack5, rustsack6])
             .expect("Creating group should not fail");
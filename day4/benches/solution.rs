@@ -0,0 +1,21 @@
+//! Benchmarks day 4 over the committed input
+use advent_common::command::AdventSolution;
+use advent_day4::solution::Day4;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const INPUT: &str = include_str!("../input.txt");
+
+fn bench_day4(c: &mut Criterion) {
+    c.bench_function("day4/parse", |b| b.iter(|| Day4::parse(black_box(INPUT))));
+
+    let parsed = Day4::parse(INPUT).expect("Committed input should parse");
+    c.bench_function("day4/part_a", |b| {
+        b.iter(|| Day4::part1(black_box(&parsed)))
+    });
+    c.bench_function("day4/part_b", |b| {
+        b.iter(|| Day4::part2(black_box(&parsed)))
+    });
+}
+
+criterion_group!(benches, bench_day4);
+criterion_main!(benches);